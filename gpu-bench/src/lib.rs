@@ -1,17 +1,23 @@
+use core_algo::verify::GPU_TARGET;
+use core_algo::{matmul_inputs_as, weighted_checksum, BenchResult, Precision, Stopwatch, DEFAULT_SEED};
 use wasm_bindgen::prelude::*;
 use wgpu::util::DeviceExt;
 
 const N: u32 = 4096;
 
+// O resultado NxN tem que caber num storage buffer de 128 MB (ver required_limits)
+const MAX_STORAGE_BYTES: u64 = 128 * 1024 * 1024;
+
 const SHADER_CODE: &str = r#"
 @group(0) @binding(0) var<storage, read> matrixA : array<f32>;
 @group(0) @binding(1) var<storage, read> matrixB : array<f32>;
 @group(0) @binding(2) var<storage, read_write> result : array<f32>;
-
-const N: u32 = 1024u;
+// x = N (o resto é só para alinhar em 16 bytes)
+@group(0) @binding(3) var<uniform> dims : vec4<u32>;
 
 @compute @workgroup_size(16, 16)
 fn main(@builtin(global_invocation_id) global_id : vec3<u32>) {
+    let N = dims.x;
     let row = global_id.x;
     let col = global_id.y;
 
@@ -35,23 +41,35 @@ fn log(s: &str) {
     web_sys::console::log_1(&JsValue::from_str(s));
 }
 
-#[wasm_bindgen]
-pub async fn run_gpu_bench() -> String {
-    std::panic::set_hook(Box::new(console_error_panic_hook::hook));
-    
-    log("=== Iniciando GPU Benchmark (Staging Fix) ===");
+// O que volta de uma multiplicação na GPU
+struct GpuRun {
+    adapter: String,
+    result: Vec<f32>,
+    elapsed_ms: f64,
+}
+
+// C = A * B (NxN, f32) na GPU. O tempo vai do submit até os dados estarem de volta na CPU
+async fn gpu_matmul(n: u32, matrix_a_host: &[f32], matrix_b_host: &[f32]) -> Result<GpuRun, String> {
+    // Buffer de staging vazio faz o slice(..) do wgpu entrar em pânico
+    if n == 0 {
+        return Err("matriz vazia: size precisa ser maior que 0".to_string());
+    }
+    let size_bytes = n as u64 * n as u64 * 4;
+    if size_bytes > MAX_STORAGE_BYTES {
+        return Err(format!("matriz {}x{} não cabe em {} MB de storage", n, n, MAX_STORAGE_BYTES >> 20));
+    }
 
     let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
     
     let adapter = instance.request_adapter(&wgpu::RequestAdapterOptions::default())
         .await
-        .expect("ERRO CRÍTICO: Não foi possível encontrar um adaptador WebGPU!");
+        .ok_or("ERRO CRÍTICO: Não foi possível encontrar um adaptador WebGPU!")?;
 
     let info = adapter.get_info();
     log(&format!("Hardware Detectado: {} ({:?})", info.name, info.backend));
 
     let mut required_limits = wgpu::Limits::downlevel_webgl2_defaults();
-    required_limits.max_storage_buffer_binding_size = 128 * 1024 * 1024; 
+    required_limits.max_storage_buffer_binding_size = MAX_STORAGE_BYTES as u32;
 
     let (device, queue) = adapter.request_device(
         &wgpu::DeviceDescriptor {
@@ -61,19 +79,18 @@ pub async fn run_gpu_bench() -> String {
             memory_hints: wgpu::MemoryHints::Performance,
         },
         None,
-    ).await.expect("ERRO: Falha ao criar dispositivo lógico (Device).");
-
-    let size_bytes = (N * N * 4) as u64;
-    let matrix_a_host = vec![1.5f32; (N * N) as usize];
-    let matrix_b_host = vec![2.5f32; (N * N) as usize];
+    ).await.map_err(|e| format!("ERRO: Falha ao criar dispositivo lógico (Device): {}", e))?;
 
     log(&format!("Alocando buffers ({:.2} MB)...", (size_bytes as f64 * 3.0)/1024.0/1024.0));
 
     let buffer_a = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-        label: Some("Matrix A"), contents: bytemuck::cast_slice(&matrix_a_host), usage: wgpu::BufferUsages::STORAGE,
+        label: Some("Matrix A"), contents: bytemuck::cast_slice(matrix_a_host), usage: wgpu::BufferUsages::STORAGE,
     });
     let buffer_b = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-        label: Some("Matrix B"), contents: bytemuck::cast_slice(&matrix_b_host), usage: wgpu::BufferUsages::STORAGE,
+        label: Some("Matrix B"), contents: bytemuck::cast_slice(matrix_b_host), usage: wgpu::BufferUsages::STORAGE,
+    });
+    let dims = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("Dims"), contents: bytemuck::cast_slice(&[n, 0, 0, 0]), usage: wgpu::BufferUsages::UNIFORM,
     });
     
     // --- CORREÇÃO: ARQUITETURA DE STAGING ---
@@ -117,6 +134,7 @@ pub async fn run_gpu_bench() -> String {
             wgpu::BindGroupEntry { binding: 1, resource: buffer_b.as_entire_binding() },
             // IMPORTANTE: O Shader liga-se ao storage_buffer (VRAM), não ao staging
             wgpu::BindGroupEntry { binding: 2, resource: storage_buffer.as_entire_binding() },
+            wgpu::BindGroupEntry { binding: 3, resource: dims.as_entire_binding() },
         ],
     });
 
@@ -125,20 +143,19 @@ pub async fn run_gpu_bench() -> String {
         let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor { label: Some("Compute Pass"), timestamp_writes: None });
         cpass.set_pipeline(&compute_pipeline);
         cpass.set_bind_group(0, &bind_group, &[]);
-        cpass.dispatch_workgroups(N / 16, N / 16, 1);
+        // Arredonda para cima: N não precisa ser múltiplo de 16 (o shader descarta o que sobra)
+        cpass.dispatch_workgroups(n.div_ceil(16), n.div_ceil(16), 1);
     }
     
     // 3. COMANDO DE CÓPIA: GPU -> Staging
     // Isso acontece na GPU, ultra rápido.
     encoder.copy_buffer_to_buffer(&storage_buffer, 0, &staging_buffer, 0, size_bytes);
 
+    // O relógio começa antes do submit: o cálculo conta, não só o download
+    let start = Stopwatch::start();
     queue.submit(Some(encoder.finish()));
 
     log("Comando enviado para GPU... Aguardando hardware.");
-    
-    let window = web_sys::window().expect("window");
-    let performance = window.performance().expect("performance");
-    let start = performance.now();
 
     // 4. Mapear o buffer de STAGING (não o de storage)
     let buffer_slice = staging_buffer.slice(..);
@@ -152,23 +169,71 @@ pub async fn run_gpu_bench() -> String {
     
     match rx.await {
         Ok(Ok(())) => log("Dados transferidos para CPU!"),
-        _ => return "ERRO FATAL: Ocorreu um erro ao ler a memória da GPU".to_string(),
+        _ => return Err("ERRO FATAL: Ocorreu um erro ao ler a memória da GPU".to_string()),
     }
 
-    let end = performance.now();
-    let duration = (end - start) / 1000.0;
+    let elapsed_ms = start.elapsed_ms();
 
-    let check_val = {
+    let result = {
         let view = buffer_slice.get_mapped_range();
-        let floats: &[f32] = bytemuck::cast_slice(&view);
-        floats[0]
+        bytemuck::cast_slice::<u8, f32>(&view).to_vec()
     };
     
     // Libera o staging para uso futuro
     staging_buffer.unmap();
 
-    format!(
-        "✅ SUCESSO!\nHardware: {}\nTempo Total (Calc + Download): {:.4}s\nMatriz: {}x{}\nCheck: {:.2}", 
-        info.name, duration, N, N, check_val
-    )
-}
\ No newline at end of file
+    Ok(GpuRun { adapter: info.name, result, elapsed_ms })
+}
+
+// As mesmas entradas (seed) e o mesmo checksum da matmul f32 do core-algo
+async fn seeded_matmul(n: u32, seed: u64) -> Result<(GpuRun, f64), String> {
+    let (a, b) = matmul_inputs_as::<f32>(n as usize, seed);
+    let run = gpu_matmul(n, a.as_slice(), b.as_slice()).await?;
+    let checksum = weighted_checksum(&run.result);
+    Ok((run, checksum))
+}
+
+#[wasm_bindgen]
+pub async fn run_gpu_bench() -> String {
+    std::panic::set_hook(Box::new(console_error_panic_hook::hook));
+    
+    log("=== Iniciando GPU Benchmark (Staging Fix) ===");
+
+    match seeded_matmul(N, DEFAULT_SEED).await {
+        Ok((run, checksum)) => format!(
+            "✅ SUCESSO!\nHardware: {}\nTempo Total (Calc + Download): {:.4}s\nMatriz: {}x{}\nChecksum: {:.6e}", 
+            run.adapter, run.elapsed_ms / 1000.0, N, N, checksum
+        ),
+        Err(e) => e,
+    }
+}
+
+// Seed vem como number do JS: só inteiros exatos (até 2^53 - 1), sem truncar em silêncio,
+// senão o resultado da GPU sai com outro seed e a verificação recusa o conjunto inteiro
+fn js_seed(seed: f64) -> Result<u64, String> {
+    const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+    if seed.fract() != 0.0 || !(0.0..=MAX_SAFE_INTEGER).contains(&seed) {
+        return Err(format!("seed inválido: {} (inteiro de 0 a 2^53 - 1)", seed));
+    }
+    Ok(seed as u64)
+}
+
+// Mesmo formato do core-algo (BenchResult, target "webgpu"), para o modo de verificação
+// do rust-bench comparar com o nativo e o wasm no mesmo seed
+#[wasm_bindgen]
+pub async fn run_gpu_matmul(size: u32, seed: Option<f64>) -> Result<JsValue, JsValue> {
+    std::panic::set_hook(Box::new(console_error_panic_hook::hook));
+
+    let seed = match seed {
+        None => DEFAULT_SEED,
+        Some(s) => js_seed(s).map_err(|e| JsValue::from_str(&e))?,
+    };
+    let (run, checksum) = seeded_matmul(size, seed).await.map_err(|e| JsValue::from_str(&e))?;
+
+    let n = size as u64;
+    let mut result = BenchResult::new("matmul", size as usize, seed, run.elapsed_ms, 1, checksum, 2 * n * n * n);
+    result.target = GPU_TARGET.to_string();
+    result.precision = Some(Precision::F32);
+    log(&format!("{} ({})", result, run.adapter));
+    Ok(serde_wasm_bindgen::to_value(&result)?)
+}
@@ -0,0 +1,627 @@
+// --- HISTÓRICO DOS RESULTADOS (SQLite) ---
+// Todo resultado que passa pelo servidor fica guardado: nativo (/api/native e jobs),
+// wasm no wasmtime (/api/wasm) e o que o browser manda (wasm e GPU, POST /api/results).
+// Cada linha leva os metadados de onde rodou: a máquina do servidor, ou o que o browser
+// contou dele (user agent, núcleos, adaptador da GPU...).
+//   GET /api/results?kernel=&size=&target=&source=&precision=&limit=   os mais recentes primeiro
+//   GET /api/compare/{kernel}/{size}?baseline=native&precision=f64     mediana por alvo e speedup
+//                                       (nas matmul, também a fração do matmul-blas nativo)
+// O banco fica em HISTORY_DB (padrão: history.sqlite3 na pasta onde o backend roda).
+
+use actix_web::{http::header, web, HttpRequest, HttpResponse, Responder};
+use core_algo::kernels::{self, BLAS_KERNEL};
+use core_algo::{verify::GPU_TARGET, BenchResult, Precision, Stats, TARGET};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const DB_ENV: &str = "HISTORY_DB";
+pub const DEFAULT_DB: &str = "history.sqlite3";
+// Linhas por GET /api/results quando ninguém pede outro limite, e o máximo aceito
+pub const DEFAULT_LIMIT: usize = 100;
+pub const MAX_LIMIT: usize = 1000;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS results (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    created_ms  INTEGER NOT NULL,
+    source      TEXT NOT NULL,
+    kernel      TEXT NOT NULL,
+    size        INTEGER NOT NULL,
+    seed        INTEGER NOT NULL,
+    target      TEXT NOT NULL,
+    threads     INTEGER NOT NULL,
+    elapsed_ms  REAL NOT NULL,
+    checksum    REAL NOT NULL,
+    ops         INTEGER NOT NULL,
+    gflops      REAL NOT NULL,
+    stats       TEXT,
+    meta        TEXT NOT NULL,
+    memory      TEXT,
+    precision   TEXT,
+    cutoff      INTEGER,
+    numeric_error TEXT
+);
+CREATE INDEX IF NOT EXISTS results_kernel_size ON results (kernel, size);
+";
+
+// Colunas que entraram depois da primeira versão da tabela: bancos antigos ganham com ALTER TABLE
+const ADDED_COLUMNS: [(&str, &str); 4] =
+    [("memory", "TEXT"), ("precision", "TEXT"), ("cutoff", "INTEGER"), ("numeric_error", "TEXT")];
+
+fn db_error(e: rusqlite::Error) -> io::Error {
+    io::Error::other(format!("histórico: {}", e))
+}
+
+// Onde o resultado foi produzido
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Source {
+    // Rodou no servidor, nativo
+    Native,
+    // Rodou no servidor, build WASI dentro do wasmtime
+    Wasmtime,
+    // Veio do browser (wasm ou WebGPU)
+    Browser,
+}
+
+impl Source {
+    fn as_str(self) -> &'static str {
+        match self {
+            Source::Native => "native",
+            Source::Wasmtime => "wasmtime",
+            Source::Browser => "browser",
+        }
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        [Source::Native, Source::Wasmtime, Source::Browser].into_iter().find(|s| s.as_str() == text)
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Record {
+    pub id: i64,
+    // Unix epoch em ms
+    pub created_ms: i64,
+    pub source: Source,
+    pub result: BenchResult,
+    pub meta: Value,
+}
+
+// Filtros do GET /api/results (todos opcionais)
+#[derive(Deserialize, Default)]
+pub struct Filter {
+    pub kernel: Option<String>,
+    pub size: Option<usize>,
+    pub target: Option<String>,
+    pub source: Option<String>,
+    pub seed: Option<u64>,
+    pub precision: Option<Precision>,
+    pub limit: Option<usize>,
+}
+
+// Um alvo (target + threads) dentro da comparação
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct TargetSummary {
+    pub target: String,
+    pub threads: usize,
+    pub runs: usize,
+    pub best_ms: f64,
+    pub median_ms: f64,
+    // mediana do baseline / mediana deste alvo: > 1 é mais rápido que o baseline
+    pub speedup: f64,
+    // mediana do matmul-blas / mediana deste alvo: 1 = tão rápido quanto o GEMM otimizado
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blas_fraction: Option<f64>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub kernel: String,
+    pub size: usize,
+    pub precision: Precision,
+    pub baseline: String,
+    pub baseline_threads: usize,
+    pub targets: Vec<TargetSummary>,
+    // Nas matmul: o matmul-blas nativo no mesmo size/precisão (o grupo com mais threads), se já rodou
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blas: Option<TargetSummary>,
+}
+
+// Metadados da máquina do servidor, guardados junto com cada execução local
+pub fn machine() -> Value {
+    let cpu_model = std::fs::read_to_string("/proc/cpuinfo").ok().and_then(|info| {
+        info.lines()
+            .find(|line| line.starts_with("model name"))
+            .and_then(|line| line.split_once(':'))
+            .map(|(_, model)| model.trim().to_string())
+    });
+    serde_json::json!({
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "cpus": std::thread::available_parallelism().map_or(1, |n| n.get()),
+        "cpu_model": cpu_model,
+    })
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_millis() as i64)
+}
+
+pub struct History {
+    conn: Mutex<Connection>,
+    machine: Value,
+}
+
+impl History {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        Self::with_connection(Connection::open(path).map_err(db_error)?)
+    }
+
+    pub fn from_env() -> io::Result<Self> {
+        let path = std::env::var_os(DB_ENV).unwrap_or_else(|| DEFAULT_DB.into());
+        Self::open(Path::new(&path))
+    }
+
+    fn with_connection(conn: Connection) -> io::Result<Self> {
+        conn.execute_batch(SCHEMA).map_err(db_error)?;
+        for (column, kind) in ADDED_COLUMNS {
+            let exists: bool = conn
+                .query_row("SELECT COUNT(*) FROM pragma_table_info('results') WHERE name = ?1", [column], |row| row.get(0))
+                .map_err(db_error)?;
+            if !exists {
+                conn.execute_batch(&format!("ALTER TABLE results ADD COLUMN {} {}", column, kind)).map_err(db_error)?;
+            }
+        }
+        Ok(History {
+            conn: Mutex::new(conn),
+            machine: machine(),
+        })
+    }
+
+    // Resultado que rodou aqui no servidor (metadados = a máquina)
+    pub fn record_local(&self, source: Source, result: &BenchResult) -> io::Result<i64> {
+        self.insert(source, result, &self.machine)
+    }
+
+    // Como record_local, mas falha do banco só vai para o log: o resultado sai igual para o cliente
+    pub fn keep(&self, source: Source, result: &BenchResult) {
+        if let Err(e) = self.record_local(source, result) {
+            println!("Não deu para guardar o resultado: {}", e);
+        }
+    }
+
+    pub fn insert(&self, source: Source, result: &BenchResult, meta: &Value) -> io::Result<i64> {
+        let stats = result.stats.as_ref().map(|s| serde_json::to_string(s).expect("Stats serializável"));
+        let memory = result.memory.as_ref().map(|m| serde_json::to_string(m).expect("MemoryStats serializável"));
+        let numeric_error = result.numeric_error.map(|e| serde_json::to_string(&e).expect("NumericError serializável"));
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO results
+                 (created_ms, source, kernel, size, seed, target, threads, elapsed_ms, checksum, ops, gflops, stats, meta, memory, precision, cutoff, numeric_error)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
+            params![
+                now_ms(),
+                source.as_str(),
+                result.kernel,
+                result.size as i64,
+                // SQLite só tem inteiro com sinal; o seed volta igual no `as u64`
+                result.seed as i64,
+                result.target,
+                result.threads as i64,
+                result.elapsed_ms,
+                result.checksum,
+                result.ops as i64,
+                result.gflops,
+                stats,
+                meta.to_string(),
+                memory,
+                result.precision.map(Precision::name),
+                result.cutoff.map(|c| c as i64),
+                numeric_error,
+            ],
+        )
+        .map_err(db_error)?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn get(&self, id: i64) -> io::Result<Option<Record>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(&format!("{} WHERE id = ?1", SELECT), [id], row_to_record)
+            .optional()
+            .map_err(db_error)
+    }
+
+    pub fn query(&self, filter: &Filter) -> io::Result<Vec<Record>> {
+        let mut clauses = Vec::new();
+        let mut values: Vec<rusqlite::types::Value> = Vec::new();
+        if let Some(kernel) = &filter.kernel {
+            clauses.push("kernel = ?");
+            values.push(kernel.clone().into());
+        }
+        if let Some(size) = filter.size {
+            clauses.push("size = ?");
+            values.push((size as i64).into());
+        }
+        if let Some(target) = &filter.target {
+            clauses.push("target = ?");
+            values.push(target.clone().into());
+        }
+        if let Some(source) = &filter.source {
+            clauses.push("source = ?");
+            values.push(source.clone().into());
+        }
+        if let Some(seed) = filter.seed {
+            clauses.push("seed = ?");
+            values.push((seed as i64).into());
+        }
+        if let Some(precision) = filter.precision {
+            clauses.push("precision = ?");
+            values.push(precision.name().to_string().into());
+        }
+        let limit = filter.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+
+        let mut sql = SELECT.to_string();
+        if !clauses.is_empty() {
+            sql += &format!(" WHERE {}", clauses.join(" AND "));
+        }
+        sql += &format!(" ORDER BY id DESC LIMIT {}", limit);
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&sql).map_err(db_error)?;
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(values), row_to_record)
+            .map_err(db_error)?;
+        rows.collect::<Result<_, _>>().map_err(db_error)
+    }
+
+    // Mediana de elapsed_ms por (target, threads) de todas as execuções guardadas de kernel/size
+    // numa precisão, e o speedup de cada um contra o baseline (o grupo do target `baseline` com
+    // mais threads, ou exatamente `baseline_threads`). Ok(None) se não há execução do baseline.
+    // Linhas sem precisão (outros kernels, ou de antes da coluna) contam como f64, ou f32 na GPU.
+    // Nas outras matmul entra também o matmul-blas nativo como referência (ver Comparison::blas)
+    pub fn compare(
+        &self,
+        kernel: &str,
+        size: usize,
+        precision: Precision,
+        baseline: &str,
+        baseline_threads: Option<usize>,
+    ) -> io::Result<Option<Comparison>> {
+        let samples = self.samples(kernel, size, precision)?;
+        let blas = if kernel.starts_with("matmul") && kernel != BLAS_KERNEL {
+            self.samples(BLAS_KERNEL, size, precision)?
+                .into_iter()
+                .filter(|(target, _, _)| target == TARGET)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        Ok(summarize(kernel, size, precision, samples, blas, baseline, baseline_threads))
+    }
+
+    // (target, threads, elapsed_ms) de cada execução guardada
+    fn samples(&self, kernel: &str, size: usize, precision: Precision) -> io::Result<Vec<(String, usize, f64)>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(
+                "SELECT target, threads, elapsed_ms FROM results
+                 WHERE kernel = ?1 AND size = ?2 AND (precision = ?3
+                    OR (precision IS NULL AND ?3 = CASE WHEN target = ?4 THEN 'f32' ELSE 'f64' END))",
+            )
+            .map_err(db_error)?;
+        let rows = stmt
+            .query_map(params![kernel, size as i64, precision.name(), GPU_TARGET], |row| {
+                Ok((row.get(0)?, row.get::<_, i64>(1)? as usize, row.get(2)?))
+            })
+            .map_err(db_error)?;
+        rows.collect::<Result<_, _>>().map_err(db_error)
+    }
+}
+
+// Estatísticas das amostras agrupadas por (target, threads)
+fn group(samples: Vec<(String, usize, f64)>) -> Vec<((String, usize), Stats)> {
+    let mut groups: BTreeMap<(String, usize), Vec<f64>> = BTreeMap::new();
+    for (target, threads, elapsed_ms) in samples {
+        groups.entry((target, threads)).or_default().push(elapsed_ms);
+    }
+    groups
+        .into_iter()
+        .map(|(key, samples)| (key, Stats::from_samples(0, samples)))
+        .collect()
+}
+
+fn summarize(
+    kernel: &str,
+    size: usize,
+    precision: Precision,
+    samples: Vec<(String, usize, f64)>,
+    blas: Vec<(String, usize, f64)>,
+    baseline: &str,
+    baseline_threads: Option<usize>,
+) -> Option<Comparison> {
+    let stats = group(samples);
+
+    let (base_key, base) = stats
+        .iter()
+        .filter(|((target, threads), _)| target == baseline && baseline_threads.is_none_or(|t| t == *threads))
+        .max_by_key(|((_, threads), _)| *threads)?;
+    let base_median = base.median;
+    let baseline_threads = base_key.1;
+    let blas = group(blas).into_iter().max_by_key(|((_, threads), _)| *threads);
+    let blas_median = blas.as_ref().map(|(_, s)| s.median);
+
+    let summary = |target: &str, threads: usize, s: &Stats| TargetSummary {
+        target: target.to_string(),
+        threads,
+        runs: s.reps,
+        best_ms: s.min,
+        median_ms: s.median,
+        speedup: if s.median > 0.0 { base_median / s.median } else { 0.0 },
+        blas_fraction: blas_median.map(|b| if s.median > 0.0 { b / s.median } else { 0.0 }),
+    };
+    let targets = stats
+        .iter()
+        .map(|((target, threads), s)| summary(target, *threads, s))
+        .collect();
+    Some(Comparison {
+        kernel: kernel.to_string(),
+        size,
+        precision,
+        baseline: baseline.to_string(),
+        baseline_threads,
+        targets,
+        blas: blas.map(|((target, threads), s)| summary(&target, threads, &s)),
+    })
+}
+
+// O que o browser manda: o BenchResult que o wasm/GPU devolveu e o que ele sabe de si
+// (núcleos, adaptador da GPU...). O user agent vem do header
+#[derive(Deserialize)]
+pub struct Submission {
+    result: BenchResult,
+    #[serde(default)]
+    meta: Value,
+}
+
+// Targets que só o browser produz; native/wasm32-wasi entram pelo servidor, não por aqui
+fn browser_target(target: &str) -> bool {
+    target == "wasm32" || target == GPU_TARGET
+}
+
+pub async fn submit(history: web::Data<History>, request: HttpRequest, body: web::Json<Submission>) -> impl Responder {
+    let Submission { result, meta } = body.into_inner();
+    if !browser_target(&result.target) {
+        return HttpResponse::BadRequest().body(format!(
+            "target '{}' não é de browser (aceitos: wasm32, {}; {} entra pelo servidor)",
+            result.target, GPU_TARGET, TARGET
+        ));
+    }
+    if kernels::find(&result.kernel).is_none() {
+        return HttpResponse::BadRequest().body("kernel desconhecido (veja /api/kernels)");
+    }
+    let mut meta = match meta {
+        Value::Object(map) => map,
+        Value::Null => Default::default(),
+        _ => return HttpResponse::BadRequest().body("meta tem que ser um objeto"),
+    };
+    if let Some(agent) = request.headers().get(header::USER_AGENT).and_then(|v| v.to_str().ok()) {
+        meta.insert("user_agent".to_string(), agent.into());
+    }
+    let meta = Value::Object(meta);
+
+    let stored = web::block(move || {
+        let id = history.insert(Source::Browser, &result, &meta)?;
+        history.get(id)
+    })
+    .await;
+    match stored {
+        Ok(Ok(Some(record))) => HttpResponse::Created()
+            .insert_header((header::LOCATION, format!("/api/results/{}", record.id)))
+            .json(record),
+        Ok(Ok(None)) | Err(_) => HttpResponse::InternalServerError().finish(),
+        Ok(Err(e)) => HttpResponse::InternalServerError().body(e.to_string()),
+    }
+}
+
+pub async fn list(history: web::Data<History>, filter: web::Query<Filter>) -> impl Responder {
+    match web::block(move || history.query(&filter)).await {
+        Ok(Ok(records)) => HttpResponse::Ok().json(records),
+        Ok(Err(e)) => HttpResponse::InternalServerError().body(e.to_string()),
+        Err(_) => HttpResponse::InternalServerError().finish(),
+    }
+}
+
+pub async fn show(history: web::Data<History>, id: web::Path<i64>) -> impl Responder {
+    let id = id.into_inner();
+    match web::block(move || history.get(id)).await {
+        Ok(Ok(Some(record))) => HttpResponse::Ok().json(record),
+        Ok(Ok(None)) => HttpResponse::NotFound().body("resultado desconhecido"),
+        Ok(Err(e)) => HttpResponse::InternalServerError().body(e.to_string()),
+        Err(_) => HttpResponse::InternalServerError().finish(),
+    }
+}
+
+// ?baseline=native (padrão) &baseline_threads=N
+#[derive(Deserialize)]
+pub struct CompareQuery {
+    baseline: Option<String>,
+    baseline_threads: Option<usize>,
+    // Padrão f64
+    precision: Option<Precision>,
+}
+
+pub async fn compare(
+    history: web::Data<History>,
+    path: web::Path<(String, usize)>,
+    query: web::Query<CompareQuery>,
+) -> impl Responder {
+    let (kernel, size) = path.into_inner();
+    let CompareQuery {
+        baseline,
+        baseline_threads,
+        precision,
+    } = query.into_inner();
+    let baseline = baseline.unwrap_or_else(|| TARGET.to_string());
+    let precision = precision.unwrap_or(Precision::F64);
+
+    let comparison = {
+        let baseline = baseline.clone();
+        web::block(move || history.compare(&kernel, size, precision, &baseline, baseline_threads)).await
+    };
+    match comparison {
+        Ok(Ok(Some(comparison))) => HttpResponse::Ok().json(comparison),
+        Ok(Ok(None)) => HttpResponse::NotFound().body(format!("nenhuma execução de {} guardada para comparar", baseline)),
+        Ok(Err(e)) => HttpResponse::InternalServerError().body(e.to_string()),
+        Err(_) => HttpResponse::InternalServerError().finish(),
+    }
+}
+
+const SELECT: &str = "SELECT id, created_ms, source, kernel, size, seed, target, threads, elapsed_ms, checksum, ops, gflops, stats, meta, memory, precision, cutoff, numeric_error FROM results";
+
+fn row_to_record(row: &rusqlite::Row) -> rusqlite::Result<Record> {
+    let source: String = row.get(2)?;
+    let stats: Option<String> = row.get(12)?;
+    let meta: String = row.get(13)?;
+    let memory: Option<String> = row.get(14)?;
+    let precision: Option<String> = row.get(15)?;
+    let numeric_error: Option<String> = row.get(17)?;
+    Ok(Record {
+        id: row.get(0)?,
+        created_ms: row.get(1)?,
+        // Só este módulo escreve na tabela: texto fora da lista seria banco corrompido
+        source: Source::parse(&source).unwrap_or(Source::Browser),
+        result: BenchResult {
+            kernel: row.get(3)?,
+            size: row.get::<_, i64>(4)? as usize,
+            seed: row.get::<_, i64>(5)? as u64,
+            target: row.get(6)?,
+            threads: row.get::<_, i64>(7)? as usize,
+            elapsed_ms: row.get(8)?,
+            checksum: row.get(9)?,
+            ops: row.get::<_, i64>(10)? as u64,
+            gflops: row.get(11)?,
+            precision: precision.and_then(|p| p.parse().ok()),
+            cutoff: row.get::<_, Option<i64>>(16)?.map(|c| c as usize),
+            numeric_error: numeric_error.and_then(|e| serde_json::from_str(&e).ok()),
+            stats: stats.and_then(|s| serde_json::from_str(&s).ok()),
+            memory: memory.and_then(|m| serde_json::from_str(&m).ok()),
+        },
+        meta: serde_json::from_str(&meta).unwrap_or(Value::Null),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_algo::{run_kernel, Mode};
+
+    fn memory() -> History {
+        History::with_connection(Connection::open_in_memory().unwrap()).unwrap()
+    }
+
+    fn result(target: &str, threads: usize, elapsed_ms: f64) -> BenchResult {
+        let mut result = run_kernel("sieve", 1000, Mode::Single).unwrap();
+        result.target = target.to_string();
+        result.threads = threads;
+        result.elapsed_ms = elapsed_ms;
+        result
+    }
+
+    #[test]
+    fn stores_and_filters() {
+        let history = memory();
+        let native = result("native", 4, 10.0);
+        let id = history.record_local(Source::Native, &native).unwrap();
+        let meta = serde_json::json!({"user_agent": "teste"});
+        history.insert(Source::Browser, &result("wasm32", 1, 20.0), &meta).unwrap();
+
+        let stored = history.get(id).unwrap().unwrap();
+        // Inclui a memória (o main.rs instala o CountingAlloc)
+        assert!(native.memory.is_some());
+        assert_eq!(stored.result, native);
+        assert_eq!(stored.source, Source::Native);
+        assert_eq!(stored.meta["arch"], std::env::consts::ARCH);
+
+        let all = history.query(&Filter::default()).unwrap();
+        assert_eq!(all.iter().map(|r| r.result.target.as_str()).collect::<Vec<_>>(), ["wasm32", "native"]);
+        let browser = Filter {
+            source: Some("browser".into()),
+            ..Filter::default()
+        };
+        assert_eq!(history.query(&browser).unwrap()[0].meta, meta);
+        let other = Filter {
+            kernel: Some("matmul".into()),
+            ..Filter::default()
+        };
+        assert!(history.query(&other).unwrap().is_empty());
+
+        // Cutoff e erro numérico das matmul recursivas voltam (o JSON pode mexer no último dígito)
+        let strassen = run_kernel("matmul-strassen", 80, Mode::Single).unwrap();
+        let id = history.record_local(Source::Native, &strassen).unwrap();
+        let stored = history.get(id).unwrap().unwrap().result;
+        assert_eq!(stored.cutoff, Some(64));
+        let (stored, original) = (stored.numeric_error.unwrap(), strassen.numeric_error.unwrap());
+        assert!((stored.relative - original.relative).abs() <= 1e-15 * original.relative);
+    }
+
+    #[test]
+    fn compares_medians_against_baseline() {
+        let history = memory();
+        for (target, threads, ms) in [("native", 1, 40.0), ("native", 4, 10.0), ("native", 4, 12.0), ("wasm32", 1, 30.0)] {
+            history.record_local(Source::Native, &result(target, threads, ms)).unwrap();
+        }
+
+        let c = history.compare("sieve", 1000, Precision::F64, "native", None).unwrap().unwrap();
+        assert_eq!(c.baseline_threads, 4);
+        let wasm = c.targets.iter().find(|t| t.target == "wasm32").unwrap();
+        assert_eq!((wasm.runs, wasm.median_ms), (1, 30.0));
+        assert!((wasm.speedup - 11.0 / 30.0).abs() < 1e-12);
+
+        let single = history.compare("sieve", 1000, Precision::F64, "native", Some(1)).unwrap().unwrap();
+        let native4 = single.targets.iter().find(|t| t.threads == 4).unwrap();
+        assert_eq!((native4.best_ms, native4.speedup), (10.0, 40.0 / 11.0));
+
+        assert!(history.compare("sieve", 1000, Precision::F64, "webgpu", None).unwrap().is_none());
+
+        // Uma precisão por comparação; a GPU sem `precision` (histórico antigo) conta como f32
+        let mut f32 = result("native", 1, 5.0);
+        f32.precision = Some(Precision::F32);
+        history.record_local(Source::Native, &f32).unwrap();
+        history.insert(Source::Browser, &result(GPU_TARGET, 1, 1.0), &Value::Null).unwrap();
+        let c = history.compare("sieve", 1000, Precision::F32, "native", None).unwrap().unwrap();
+        assert_eq!(c.targets.iter().map(|t| t.target.as_str()).collect::<Vec<_>>(), ["native", GPU_TARGET]);
+        assert_eq!(c.targets[1].speedup, 5.0);
+        assert_eq!(history.compare("sieve", 1000, Precision::F64, "native", None).unwrap().unwrap().targets.len(), 3);
+        let stored = Filter {
+            precision: Some(Precision::F32),
+            ..Filter::default()
+        };
+        assert_eq!(history.query(&stored).unwrap()[0].result, f32);
+        assert!(c.blas.is_none() && c.targets[0].blas_fraction.is_none());
+
+        // Nas matmul, o matmul-blas nativo com mais threads vira a régua
+        for (kernel, target, threads, ms) in [
+            ("matmul-tiled", "native", 4, 20.0),
+            ("matmul-tiled", "wasm32", 1, 80.0),
+            (BLAS_KERNEL, "native", 1, 8.0),
+            (BLAS_KERNEL, "native", 4, 2.0),
+            (BLAS_KERNEL, "wasm32", 8, 1.0),
+        ] {
+            let mut r = result(target, threads, ms);
+            r.kernel = kernel.to_string();
+            history.record_local(Source::Native, &r).unwrap();
+        }
+        let c = history.compare("matmul-tiled", 1000, Precision::F64, "native", None).unwrap().unwrap();
+        let blas = c.blas.unwrap();
+        assert_eq!((blas.threads, blas.median_ms, blas.speedup), (4, 2.0, 10.0));
+        assert_eq!(c.targets.iter().map(|t| t.blas_fraction.unwrap()).collect::<Vec<_>>(), [0.1, 0.025]);
+        assert!(history.compare(BLAS_KERNEL, 1000, Precision::F64, "native", None).unwrap().unwrap().blas.is_none());
+    }
+}
@@ -0,0 +1,242 @@
+// --- JOBS ASSÍNCRONOS ---
+// Uma matmul 4000x4000 passa fácil do timeout de um GET. Aqui o benchmark vira um job:
+//   POST   /api/jobs       {kernel, size, warmup?, reps?, seed?, threads?, precision?, cutoff?} -> 202 + Location
+//   GET    /api/jobs/{id}  estado, progresso (0..1) e, no fim, o BenchResult
+//   DELETE /api/jobs/{id}  cancela se está rodando; se já terminou, esquece o job
+// O kernel avisa o progresso e confere o CancelToken entre linhas/blocos (core_algo::control).
+
+use crate::history::{History, Source};
+use crate::limits::{admit, Limits};
+use actix_web::{http::header, web, HttpResponse, Responder};
+use core_algo::{bench_kernel_with, check_threads, thread_pool, BenchConfig, BenchResult, CancelToken, Cancelled, Control, Mode, Precision};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+// Jobs terminados guardados para consulta; acima disso os mais antigos são esquecidos
+const MAX_FINISHED: usize = 100;
+
+#[derive(Deserialize)]
+pub struct JobRequest {
+    kernel: String,
+    size: usize,
+    warmup: Option<usize>,
+    reps: Option<usize>,
+    seed: Option<u64>,
+    // Como no ?threads do /api/native: sem, usa o pool global do rayon
+    threads: Option<usize>,
+    // f32 | f64 | i32 | i64 nas matmul (padrão f64)
+    precision: Option<Precision>,
+    // Nas matmul recursivas (padrão strassen::DEFAULT_CUTOFF)
+    cutoff: Option<usize>,
+}
+
+enum State {
+    Running,
+    Done(Box<BenchResult>),
+    Failed(String),
+    Cancelled,
+}
+
+pub struct Job {
+    id: u64,
+    kernel: String,
+    size: usize,
+    // Bits do f64 da fração concluída (f64 >= 0 tem a mesma ordem que os bits, dá para usar fetch_max)
+    progress: AtomicU64,
+    cancel: CancelToken,
+    state: Mutex<State>,
+    started: Instant,
+    wall: OnceLock<Duration>,
+}
+
+// O que o GET devolve
+#[derive(Serialize)]
+struct JobView {
+    id: u64,
+    kernel: String,
+    size: usize,
+    // running | cancelling | done | failed | cancelled
+    status: &'static str,
+    progress: f64,
+    // Tempo de parede do job inteiro (aquecimento + repetições), não a mediana
+    wall_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<BenchResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl Job {
+    fn is_running(&self) -> bool {
+        matches!(*self.state.lock().unwrap(), State::Running)
+    }
+
+    fn view(&self) -> JobView {
+        let state = self.state.lock().unwrap();
+        let (status, result, error) = match &*state {
+            State::Running if self.cancel.is_cancelled() => ("cancelling", None, None),
+            State::Running => ("running", None, None),
+            State::Done(result) => ("done", Some((**result).clone()), None),
+            State::Failed(e) => ("failed", None, Some(e.clone())),
+            State::Cancelled => ("cancelled", None, None),
+        };
+        JobView {
+            id: self.id,
+            kernel: self.kernel.clone(),
+            size: self.size,
+            status,
+            progress: f64::from_bits(self.progress.load(Ordering::Relaxed)),
+            wall_ms: self.wall.get().copied().unwrap_or_else(|| self.started.elapsed()).as_secs_f64() * 1000.0,
+            result,
+            error,
+        }
+    }
+
+    fn finish(&self, state: State) {
+        self.wall.get_or_init(|| self.started.elapsed());
+        *self.state.lock().unwrap() = state;
+    }
+
+    // Roda no pool de bloqueio (web::block); no modo paralelo o progresso chega de várias threads
+    fn run(&self, config: BenchConfig, pool: Option<rayon::ThreadPool>, history: &History) {
+        let report = |fraction: f64| {
+            self.progress.fetch_max(fraction.to_bits(), Ordering::Relaxed);
+        };
+        let ctl = Control::new(Some(&self.cancel), Some(&report));
+        let bench = || bench_kernel_with(&self.kernel, self.size, Mode::Parallel, config, &ctl);
+        let outcome = match pool {
+            Some(pool) => pool.install(bench),
+            None => bench(),
+        };
+
+        self.finish(match outcome {
+            Ok(Some(result)) => {
+                println!("Job {}: {}", self.id, result);
+                history.keep(Source::Native, &result);
+                State::Done(Box::new(result))
+            }
+            Ok(None) => State::Failed("kernel desconhecido".to_string()),
+            Err(Cancelled) => {
+                println!("Job {}: cancelado", self.id);
+                State::Cancelled
+            }
+        });
+    }
+}
+
+#[derive(Default)]
+pub struct Jobs {
+    next_id: AtomicU64,
+    jobs: Mutex<HashMap<u64, Arc<Job>>>,
+}
+
+impl Jobs {
+    fn insert(&self, kernel: String, size: usize) -> Arc<Job> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let job = Arc::new(Job {
+            id,
+            kernel,
+            size,
+            progress: AtomicU64::new(0.0f64.to_bits()),
+            cancel: CancelToken::new(),
+            state: Mutex::new(State::Running),
+            started: Instant::now(),
+            wall: OnceLock::new(),
+        });
+
+        let mut jobs = self.jobs.lock().unwrap();
+        let mut finished: Vec<u64> = jobs.values().filter(|j| !j.is_running()).map(|j| j.id).collect();
+        if finished.len() >= MAX_FINISHED {
+            finished.sort_unstable();
+            for id in &finished[..=finished.len() - MAX_FINISHED] {
+                jobs.remove(id);
+            }
+        }
+        jobs.insert(id, job.clone());
+        job
+    }
+
+    fn get(&self, id: u64) -> Option<Arc<Job>> {
+        self.jobs.lock().unwrap().get(&id).cloned()
+    }
+
+    fn remove(&self, id: u64) {
+        self.jobs.lock().unwrap().remove(&id);
+    }
+}
+
+pub async fn create(
+    jobs: web::Data<Jobs>,
+    limits: web::Data<Limits>,
+    history: web::Data<History>,
+    request: web::Json<JobRequest>,
+) -> impl Responder {
+    let request = request.into_inner();
+    let config = match BenchConfig::new(request.warmup, request.reps, request.seed) {
+        Ok(config) => BenchConfig {
+            precision: request.precision,
+            cutoff: request.cutoff,
+            ..config
+        },
+        Err(e) => return HttpResponse::BadRequest().body(e),
+    };
+    // Um ?threads fora da faixa vira 400 agora, não um job que falha depois
+    if let Some(Err(e)) = request.threads.map(check_threads) {
+        return HttpResponse::BadRequest().body(e);
+    }
+    // Os limites valem para o job inteiro: a vaga fica presa até ele terminar (ou ser cancelado)
+    let permit = match admit(&limits, &request.kernel, request.size, &config) {
+        Ok(permit) => permit,
+        Err(response) => return response,
+    };
+    // Só com a vaga garantida o pool sobe: um job recusado (429/503) não cria thread nenhuma
+    let pool = match request.threads.map(thread_pool).transpose() {
+        Ok(pool) => pool,
+        Err(e) => return HttpResponse::InternalServerError().body(e),
+    };
+
+    let job = jobs.insert(request.kernel, request.size);
+    println!("Job {}: {} com tamanho {}...", job.id, job.kernel, job.size);
+
+    let runner = job.clone();
+    actix_web::rt::spawn(async move {
+        let worker = runner.clone();
+        // Err só se o kernel entrou em pânico (ex.: size grande demais para a memória)
+        let run = move || {
+            let _permit = permit;
+            worker.run(config, pool, &history)
+        };
+        if web::block(run).await.is_err() {
+            runner.finish(State::Failed("o kernel abortou (panic)".to_string()));
+        }
+    });
+
+    HttpResponse::Accepted()
+        .insert_header((header::LOCATION, format!("/api/jobs/{}", job.id)))
+        .json(job.view())
+}
+
+pub async fn status(jobs: web::Data<Jobs>, id: web::Path<u64>) -> impl Responder {
+    match jobs.get(id.into_inner()) {
+        Some(job) => HttpResponse::Ok().json(job.view()),
+        None => HttpResponse::NotFound().body("job desconhecido"),
+    }
+}
+
+pub async fn cancel(jobs: web::Data<Jobs>, id: web::Path<u64>) -> impl Responder {
+    let id = id.into_inner();
+    let Some(job) = jobs.get(id) else {
+        return HttpResponse::NotFound().body("job desconhecido");
+    };
+    if job.is_running() {
+        // O kernel para na próxima linha/bloco; o GET mostra "cancelling" até lá
+        job.cancel.cancel();
+        HttpResponse::Accepted().json(job.view())
+    } else {
+        jobs.remove(id);
+        HttpResponse::NoContent().finish()
+    }
+}
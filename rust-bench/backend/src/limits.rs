@@ -0,0 +1,242 @@
+// --- LIMITES DE ENTRADA E ADMISSÃO ---
+// Qualquer visitante pode pedir /api/native/matmul/100000 (quatro matrizes de 80 GB).
+// Antes de alocar, cada execução passa por aqui:
+//   - size acima do máximo do kernel, ou estimativa de memória acima do limite por execução: 400
+//   - já há MAX_RUNNING execuções em andamento: 429
+//   - a soma das estimativas das execuções em andamento passaria de MAX_MEMORY_MB: 503
+// O Permit devolvido segura a vaga (e a memória reservada) até ser solto, no fim do web::block.
+//
+// Variáveis (todas opcionais):
+//   MAX_SIZES="matmul=4000,sieve=100000000"  troca o máximo de size de alguns kernels
+//   MAX_REQUEST_MB=1024                     memória estimada de uma execução
+//   MAX_MEMORY_MB=2048                      memória estimada somando as execuções em andamento
+//   MAX_RUNNING=2                           execuções ao mesmo tempo
+
+use actix_web::{http::header, HttpResponse};
+use core_algo::{kernels, BenchConfig, Kernel};
+use std::collections::HashMap;
+use std::io;
+use std::sync::{Arc, Mutex};
+
+pub const MAX_SIZES_ENV: &str = "MAX_SIZES";
+pub const MAX_REQUEST_ENV: &str = "MAX_REQUEST_MB";
+pub const MAX_MEMORY_ENV: &str = "MAX_MEMORY_MB";
+pub const MAX_RUNNING_ENV: &str = "MAX_RUNNING";
+
+const MIB: u64 = 1024 * 1024;
+// Sugestão para o cliente tentar de novo (429/503), em segundos
+const RETRY_AFTER: u64 = 5;
+
+fn invalid(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
+
+pub struct Limits {
+    max_sizes: HashMap<&'static str, usize>,
+    request_bytes: u64,
+    total_bytes: u64,
+    max_running: usize,
+    usage: Mutex<Usage>,
+}
+
+#[derive(Default)]
+struct Usage {
+    running: usize,
+    bytes: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Rejection {
+    // Nunca vai caber com a configuração atual (400)
+    TooLarge(String),
+    // Todas as vagas ocupadas (429)
+    Busy { running: usize },
+    // Vaga livre, mas a memória reservada pelas outras execuções não deixa (503)
+    OutOfMemory { needed: u64, available: u64 },
+}
+
+impl Rejection {
+    pub fn response(&self) -> HttpResponse {
+        match self {
+            Rejection::TooLarge(e) => HttpResponse::BadRequest().body(e.clone()),
+            Rejection::Busy { running } => HttpResponse::TooManyRequests()
+                .insert_header((header::RETRY_AFTER, RETRY_AFTER))
+                .body(format!("{} execuções em andamento; tente de novo daqui a pouco", running)),
+            Rejection::OutOfMemory { needed, available } => HttpResponse::ServiceUnavailable()
+                .insert_header((header::RETRY_AFTER, RETRY_AFTER))
+                .body(format!(
+                    "precisa de ~{} MiB e só há {} MiB livres no orçamento; tente de novo daqui a pouco",
+                    needed.div_ceil(MIB),
+                    available / MIB
+                )),
+        }
+    }
+}
+
+// Vaga de uma execução; solta a vaga e a memória reservada no drop
+pub struct Permit {
+    limits: Arc<Limits>,
+    bytes: u64,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let mut usage = self.limits.usage.lock().unwrap();
+        usage.running -= 1;
+        usage.bytes -= self.bytes;
+    }
+}
+
+// "matmul=4000,sieve=100000000" -> {matmul: 4000, sieve: 100000000}; kernel desconhecido é erro
+fn parse_max_sizes(text: &str) -> Result<HashMap<&'static str, usize>, String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (name, size) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("esperado kernel=size, veio '{}'", entry))?;
+            let kernel = kernels::find(name.trim()).ok_or_else(|| format!("kernel desconhecido '{}'", name))?;
+            let size = size.trim().parse().map_err(|e| format!("size de {}: {}", name, e))?;
+            Ok((kernel.name(), size))
+        })
+        .collect()
+}
+
+fn env_number(name: &str, default: u64) -> io::Result<u64> {
+    match std::env::var(name) {
+        Ok(value) => value.trim().parse().map_err(|e| invalid(format!("{}: {}", name, e))),
+        Err(_) => Ok(default),
+    }
+}
+
+impl Limits {
+    pub fn new(max_sizes: HashMap<&'static str, usize>, request_bytes: u64, total_bytes: u64, max_running: usize) -> Self {
+        Limits {
+            max_sizes,
+            request_bytes,
+            total_bytes,
+            max_running,
+            usage: Mutex::new(Usage::default()),
+        }
+    }
+
+    pub fn from_env() -> io::Result<Self> {
+        let max_sizes = match std::env::var(MAX_SIZES_ENV) {
+            Ok(text) => parse_max_sizes(&text).map_err(|e| invalid(format!("{}: {}", MAX_SIZES_ENV, e)))?,
+            Err(_) => HashMap::new(),
+        };
+        let request_bytes = env_number(MAX_REQUEST_ENV, 1024)? * MIB;
+        let total_bytes = env_number(MAX_MEMORY_ENV, 2048)? * MIB;
+        let max_running = env_number(MAX_RUNNING_ENV, 2)? as usize;
+        if max_running == 0 {
+            return Err(invalid(format!("{} precisa ser pelo menos 1", MAX_RUNNING_ENV)));
+        }
+        Ok(Self::new(max_sizes, request_bytes, total_bytes, max_running))
+    }
+
+    pub fn max_size(&self, kernel: &dyn Kernel) -> usize {
+        self.max_sizes.get(kernel.name()).copied().unwrap_or_else(|| kernel.max_size())
+    }
+
+    // Para o log da subida
+    pub fn summary(&self) -> String {
+        format!(
+            "até {} execuções, {} MiB por execução, {} MiB no total",
+            self.max_running,
+            self.request_bytes / MIB,
+            self.total_bytes / MIB
+        )
+    }
+
+    // Confere size e memória estimada e reserva uma vaga (sem esperar: cheio é 429/503)
+    pub fn admit(self: &Arc<Self>, kernel: &dyn Kernel, size: usize) -> Result<Permit, Rejection> {
+        let max_size = self.max_size(kernel);
+        if size > max_size {
+            return Err(Rejection::TooLarge(format!(
+                "size {} acima do máximo de {} ({})",
+                size,
+                kernel.name(),
+                max_size
+            )));
+        }
+        let bytes = kernel.memory_bytes(size);
+        if bytes > self.request_bytes {
+            return Err(Rejection::TooLarge(format!(
+                "{} com size {} precisa de ~{} MiB; o limite por execução é {} MiB",
+                kernel.name(),
+                size,
+                bytes.div_ceil(MIB),
+                self.request_bytes / MIB
+            )));
+        }
+
+        let mut usage = self.usage.lock().unwrap();
+        if usage.running >= self.max_running {
+            return Err(Rejection::Busy { running: usage.running });
+        }
+        let available = self.total_bytes.saturating_sub(usage.bytes);
+        if bytes > available {
+            return Err(Rejection::OutOfMemory { needed: bytes, available });
+        }
+        usage.running += 1;
+        usage.bytes += bytes;
+        Ok(Permit {
+            limits: self.clone(),
+            bytes,
+        })
+    }
+}
+
+// Kernel existe + passa pelos limites (size, memória estimada, vagas): 404/400/429/503 se não
+pub fn admit(
+    limits: &Arc<Limits>,
+    kernel: &str,
+    size: usize,
+    config: &BenchConfig,
+) -> Result<Permit, HttpResponse> {
+    if kernels::find(kernel).is_none() {
+        return Err(HttpResponse::NotFound().body("kernel desconhecido (veja /api/kernels)"));
+    }
+    // A memória estimada depende do tipo do elemento (f32 é metade do f64)
+    let found = kernels::resolve(kernel, config).map_err(|e| HttpResponse::BadRequest().body(e))?;
+    limits.admit(found, size).map_err(|rejection| {
+        println!("Recusado {} com tamanho {}: {:?}", kernel, size, rejection);
+        rejection.response()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_max_sizes() {
+        let sizes = parse_max_sizes(" matmul=4000, sieve = 1000 ,").unwrap();
+        assert_eq!(sizes, HashMap::from([("matmul", 4000), ("sieve", 1000)]));
+        assert!(parse_max_sizes("nao-existe=1").is_err());
+        assert!(parse_max_sizes("matmul").is_err());
+        assert!(parse_max_sizes("matmul=muito").is_err());
+    }
+
+    #[test]
+    fn admits_within_budget() {
+        let matmul = kernels::find("matmul-tiled").unwrap();
+        // 1000x1000: 3 matrizes de ~7.6 MiB; 1100x1100: ~27.7 MiB
+        let limits = Arc::new(Limits::new(HashMap::from([("matmul-tiled", 2000)]), 32 * MIB, 50 * MIB, 2));
+
+        assert!(matches!(limits.admit(matmul, 2001), Err(Rejection::TooLarge(_))));
+        assert!(matches!(limits.admit(matmul, 2000), Err(Rejection::TooLarge(_))));
+
+        let first = limits.admit(matmul, 1000).unwrap();
+        let second = limits.admit(matmul, 1000).unwrap();
+        assert!(matches!(limits.admit(matmul, 10), Err(Rejection::Busy { running: 2 })));
+        drop(second);
+
+        // Vaga livre, mas 22.9 MiB + 27.7 MiB passa dos 50 MiB
+        assert!(matches!(limits.admit(matmul, 1100), Err(Rejection::OutOfMemory { .. })));
+        let small = limits.admit(matmul, 10).unwrap();
+        drop((first, small));
+        assert_eq!(limits.usage.lock().unwrap().bytes, 0);
+    }
+}
@@ -1,31 +1,331 @@
 use actix_files::Files;
-use actix_web::{web, App, HttpServer, HttpResponse, Responder};
-use core_algo::heavy_computation;
+use actix_web::{middleware, web, App, HttpServer, HttpResponse, Responder};
+use core_algo::{bench_kernel, bench_with_threads, catalog, kernels, sweep, verify, BenchConfig, Mode, Precision};
+use history::{History, Source};
+use jobs::Jobs;
+use limits::{admit, Limits};
+use serde::Deserialize;
+use std::sync::Arc;
+use wasm::WasmRunner;
+
+mod history;
+mod jobs;
+mod limits;
+mod wasm;
+
+// Pico de memória e alocações em cada resultado (ver core_algo::memory)
+#[global_allocator]
+static ALLOC: core_algo::CountingAlloc = core_algo::CountingAlloc;
+
+// Lista dos kernels disponíveis (o index.html monta o <select> com isso),
+// com o size máximo que este servidor aceita
+async fn list_kernels(limits: web::Data<Limits>) -> impl Responder {
+    let catalog: Vec<_> = catalog()
+        .into_iter()
+        .map(|mut info| {
+            info.max_size = limits.max_size(kernels::find(info.name).expect("veio do registro"));
+            info
+        })
+        .collect();
+    HttpResponse::Ok().json(catalog)
+}
+
+// ?warmup=N&reps=M&seed=S (padrão do harness: 1 aquecimento, 5 repetições, seed 42)
+// &threads=T roda num pool só dele (sem: pool global do rayon)
+// &precision=f32|f64|i32|i64 nas matmul (padrão f64)
+// &cutoff=C nas matmul recursivas (matmul-recursive, matmul-strassen)
+#[derive(Deserialize)]
+struct BenchQuery {
+    warmup: Option<usize>,
+    reps: Option<usize>,
+    seed: Option<u64>,
+    threads: Option<usize>,
+    precision: Option<Precision>,
+    cutoff: Option<usize>,
+}
+
+impl BenchQuery {
+    fn config(&self) -> Result<BenchConfig, HttpResponse> {
+        let mut config =
+            BenchConfig::new(self.warmup, self.reps, self.seed).map_err(|e| HttpResponse::BadRequest().body(e))?;
+        config.precision = self.precision;
+        config.cutoff = self.cutoff;
+        Ok(config)
+    }
+}
 
 // Rota que roda NATIVO (no servidor Linux)
-async fn run_native(path: web::Path<usize>) -> impl Responder {
-    let size = path.into_inner();
-    println!("Iniciando Nativo (ELF) com tamanho {}...", size);
+async fn run_native(
+    history: web::Data<History>,
+    limits: web::Data<Limits>,
+    path: web::Path<(String, usize)>,
+    query: web::Query<BenchQuery>,
+) -> impl Responder {
+    let (kernel, size) = path.into_inner();
+    let config = match query.config() {
+        Ok(config) => config,
+        Err(response) => return response,
+    };
+    let permit = match admit(&limits, &kernel, size, &config) {
+        Ok(permit) => permit,
+        Err(response) => return response,
+    };
+    let threads = query.threads;
+    println!("Iniciando Nativo (ELF) {} com tamanho {}...", kernel, size);
     
     // CORREÇÃO: Removi o .unwrap() do final desta linha
-    // Agora 'result' será um Result<String, BlockingError>
-    let result = web::block(move || heavy_computation(size)).await;
+    // Agora 'result' será um Result<Result<Option<BenchResult>, String>, BlockingError>
+    // (o permit vai junto e só libera a vaga quando o kernel termina)
+    let result = web::block(move || {
+        let _permit = permit;
+        let result = match threads {
+            Some(threads) => bench_with_threads(&kernel, size, config, threads),
+            None => Ok(bench_kernel(&kernel, size, Mode::Parallel, config)),
+        };
+        if let Ok(Some(bench)) = &result {
+            history.keep(Source::Native, bench);
+        }
+        result
+    })
+    .await;
     
     match result {
-        Ok(msg) => HttpResponse::Ok().body(msg),
+        Ok(Ok(Some(bench))) => {
+            println!("{}", bench);
+            HttpResponse::Ok().json(bench)
+        }
+        Ok(Ok(None)) => HttpResponse::NotFound().body("kernel desconhecido (veja /api/kernels)"),
+        Ok(Err(e)) => HttpResponse::BadRequest().body(e),
+        Err(_) => HttpResponse::InternalServerError().finish(),
+    }
+}
+
+// Rota antiga: /api/native/{size} continua sendo a matmul
+async fn run_native_matmul(
+    history: web::Data<History>,
+    limits: web::Data<Limits>,
+    path: web::Path<usize>,
+    query: web::Query<BenchQuery>,
+) -> impl Responder {
+    run_native(history, limits, web::Path::from(("matmul".to_string(), path.into_inner())), query).await
+}
+
+// Mesmo kernel, mas como wasm (WASI) rodando aqui dentro com wasmtime
+async fn run_wasm(
+    history: web::Data<History>,
+    limits: web::Data<Limits>,
+    runner: web::Data<Option<Arc<WasmRunner>>>,
+    path: web::Path<(String, usize)>,
+    query: web::Query<BenchQuery>,
+) -> impl Responder {
+    let (kernel, size) = path.into_inner();
+    let config = match query.config() {
+        Ok(config) => config,
+        Err(response) => return response,
+    };
+    if query.threads.is_some() {
+        return HttpResponse::BadRequest().body("o wasm no servidor roda numa thread só (sem ?threads)");
+    }
+    let Some(runner) = runner.get_ref().clone() else {
+        return HttpResponse::ServiceUnavailable()
+            .body(format!("módulo wasm não carregado (compile com: {})", wasm::BUILD_HINT));
+    };
+    let permit = match admit(&limits, &kernel, size, &config) {
+        Ok(permit) => permit,
+        Err(response) => return response,
+    };
+    println!("Iniciando WASM (wasmtime) {} com tamanho {}...", kernel, size);
+
+    match web::block(move || {
+        let _permit = permit;
+        let result = runner.run(&kernel, size, config)?;
+        history.keep(Source::Wasmtime, &result);
+        Ok::<_, std::io::Error>(result)
+    })
+    .await
+    {
+        Ok(Ok(bench)) => {
+            println!("{}", bench);
+            HttpResponse::Ok().json(bench)
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().body(e.to_string()),
+        Err(_) => HttpResponse::InternalServerError().finish(),
+    }
+}
+
+async fn run_wasm_matmul(
+    history: web::Data<History>,
+    limits: web::Data<Limits>,
+    runner: web::Data<Option<Arc<WasmRunner>>>,
+    path: web::Path<usize>,
+    query: web::Query<BenchQuery>,
+) -> impl Responder {
+    run_wasm(history, limits, runner, web::Path::from(("matmul".to_string(), path.into_inner())), query).await
+}
+
+// Curva de strong scaling: o mesmo kernel de 1 até max_threads threads
+// (padrão: núcleos da máquina), com speedup e eficiência em relação a 1 thread
+// (campos repetidos do BenchQuery: #[serde(flatten)] não converte números na query string)
+#[derive(Deserialize)]
+struct SweepQuery {
+    max_threads: Option<usize>,
+    warmup: Option<usize>,
+    reps: Option<usize>,
+    seed: Option<u64>,
+    precision: Option<Precision>,
+    cutoff: Option<usize>,
+}
+
+async fn run_sweep(
+    limits: web::Data<Limits>,
+    path: web::Path<(String, usize)>,
+    query: web::Query<SweepQuery>,
+) -> impl Responder {
+    let (kernel, size) = path.into_inner();
+    let config = match BenchConfig::new(query.warmup, query.reps, query.seed) {
+        Ok(config) => BenchConfig {
+            precision: query.precision,
+            cutoff: query.cutoff,
+            ..config
+        },
+        Err(e) => return HttpResponse::BadRequest().body(e),
+    };
+    let max_threads = query
+        .max_threads
+        .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()));
+    let permit = match admit(&limits, &kernel, size, &config) {
+        Ok(permit) => permit,
+        Err(response) => return response,
+    };
+    println!("Sweep {} com tamanho {} (1..={} threads)...", kernel, size, max_threads);
+
+    match web::block(move || {
+        let _permit = permit;
+        sweep(&kernel, size, config, max_threads)
+    })
+    .await
+    {
+        Ok(Ok(Some(scaling))) => {
+            for p in &scaling.points {
+                println!("  {} threads: {:.2}ms, speedup {:.2}x, eficiência {:.0}%", p.threads, p.elapsed_ms, p.speedup, p.efficiency * 100.0);
+            }
+            HttpResponse::Ok().json(scaling)
+        }
+        Ok(Ok(None)) => HttpResponse::NotFound().body("kernel desconhecido (veja /api/kernels)"),
+        Ok(Err(e)) => HttpResponse::BadRequest().body(e),
+        Err(_) => HttpResponse::InternalServerError().finish(),
+    }
+}
+
+// Modo de verificação: o mesmo kernel/size/seed no nativo e no wasm (wasmtime),
+// uma execução de cada, e os checksums comparados com a tolerância de cada alvo
+#[derive(Deserialize)]
+struct VerifyQuery {
+    seed: Option<u64>,
+    precision: Option<Precision>,
+    cutoff: Option<usize>,
+}
+
+async fn run_verify(
+    limits: web::Data<Limits>,
+    runner: web::Data<Option<Arc<WasmRunner>>>,
+    path: web::Path<(String, usize)>,
+    query: web::Query<VerifyQuery>,
+) -> impl Responder {
+    let (kernel, size) = path.into_inner();
+    let config = match BenchConfig::new(Some(0), Some(1), query.seed) {
+        Ok(config) => BenchConfig {
+            precision: query.precision,
+            cutoff: query.cutoff,
+            ..config
+        },
+        Err(e) => return HttpResponse::BadRequest().body(e),
+    };
+    let Some(runner) = runner.get_ref().clone() else {
+        return HttpResponse::ServiceUnavailable()
+            .body(format!("módulo wasm não carregado (compile com: {})", wasm::BUILD_HINT));
+    };
+    // Nativo e wasm rodam um depois do outro: uma vaga só
+    let permit = match admit(&limits, &kernel, size, &config) {
+        Ok(permit) => permit,
+        Err(response) => return response,
+    };
+    println!("Verificando {} com tamanho {} (seed {})...", kernel, size, config.seed);
+
+    let verification = web::block(move || {
+        let _permit = permit;
+        let native = bench_kernel(&kernel, size, Mode::Parallel, config).expect("kernel existe");
+        let wasm = runner.run(&kernel, size, config).map_err(|e| e.to_string())?;
+        verify(&[native, wasm])
+    })
+    .await;
+
+    match verification {
+        Ok(Ok(v)) => {
+            println!("Verificação {}: {}", v.kernel, if v.ok { "ok" } else { "DIVERGENTE" });
+            HttpResponse::Ok().json(v)
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().body(e),
         Err(_) => HttpResponse::InternalServerError().finish(),
     }
 }
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    println!("Servidor rodando em http://127.0.0.1:8080");
-    HttpServer::new(|| {
+    // TLS só se TLS_CERT e TLS_KEY estiverem definidas
+    let tls_config = tls_config::from_env()?;
+    let scheme = if tls_config.is_some() { "https" } else { "http" };
+
+    // Sem o módulo WASI o servidor sobe igual; só /api/wasm responde 503
+    let wasm_runner = match WasmRunner::from_env() {
+        Ok(runner) => Some(Arc::new(runner)),
+        Err(e) => {
+            println!("WASM no servidor desativado ({})", e);
+            None
+        }
+    };
+    let wasm_runner = web::Data::new(wasm_runner);
+    let jobs = web::Data::new(Jobs::default());
+    let limits = web::Data::new(Limits::from_env()?);
+    let history = web::Data::new(History::from_env()?);
+    println!("Limites: {}", limits.summary());
+
+    println!("Servidor rodando em {}://127.0.0.1:8080", scheme);
+    let server = HttpServer::new(move || {
         App::new()
-            .route("/api/native/{size}", web::get().to(run_native))
+            .app_data(wasm_runner.clone())
+            .app_data(jobs.clone())
+            .app_data(limits.clone())
+            .app_data(history.clone())
+            // Resultados do browser (BenchResult + metadados) passam fácil do limite padrão de 32 KiB
+            .app_data(web::JsonConfig::default().limit(1 << 20))
+            // Isolamento cross-origin: sem isso o browser não libera SharedArrayBuffer (wasm com threads)
+            .wrap(
+                middleware::DefaultHeaders::new()
+                    .add(("Cross-Origin-Opener-Policy", "same-origin"))
+                    .add(("Cross-Origin-Embedder-Policy", "require-corp")),
+            )
+            .route("/api/kernels", web::get().to(list_kernels))
+            .route("/api/native/{kernel}/{size}", web::get().to(run_native))
+            .route("/api/native/{size}", web::get().to(run_native_matmul))
+            .route("/api/wasm/{kernel}/{size}", web::get().to(run_wasm))
+            .route("/api/wasm/{size}", web::get().to(run_wasm_matmul))
+            .route("/api/verify/{kernel}/{size}", web::get().to(run_verify))
+            .route("/api/sweep/{kernel}/{size}", web::get().to(run_sweep))
+            .route("/api/results", web::post().to(history::submit))
+            .route("/api/results", web::get().to(history::list))
+            .route("/api/results/{id}", web::get().to(history::show))
+            .route("/api/compare/{kernel}/{size}", web::get().to(history::compare))
+            .route("/api/jobs", web::post().to(jobs::create))
+            .route("/api/jobs/{id}", web::get().to(jobs::status))
+            .route("/api/jobs/{id}", web::delete().to(jobs::cancel))
             .service(Files::new("/", "./static").index_file("index.html"))
-    })
-    .bind(("0.0.0.0", 8080))?
+    });
+
+    match tls_config {
+        Some(config) => server.bind_rustls_0_23(("0.0.0.0", 8080), config)?,
+        None => server.bind(("0.0.0.0", 8080))?,
+    }
     .run()
     .await
 }
\ No newline at end of file
@@ -0,0 +1,141 @@
+// --- WASM NO SERVIDOR (wasmtime) ---
+// Comparar o wasm do browser com o nativo do servidor mistura duas máquinas.
+// Aqui o build WASI do core-algo (bin core-algo-wasi) roda dentro do próprio backend,
+// então /api/wasm e /api/native medem o mesmo hardware.
+// O módulo é compilado (Cranelift) uma vez na subida; cada requisição instancia de novo.
+
+use core_algo::{BenchConfig, BenchResult, Precision};
+use std::io;
+use std::path::Path;
+use wasmtime::{Engine, InstancePre, Linker, Module, Store};
+use wasmtime_wasi::p1::{self, WasiP1Ctx};
+use wasmtime_wasi::p2::pipe::MemoryOutputPipe;
+use wasmtime_wasi::{I32Exit, WasiCtxBuilder};
+
+pub const MODULE_ENV: &str = "WASM_MODULE";
+pub const DEFAULT_MODULE: &str = "target/wasm32-wasip1/release/core-algo-wasi.wasm";
+pub const BUILD_HINT: &str =
+    "cargo build -p core-algo --bin core-algo-wasi --release --target wasm32-wasip1";
+
+// O guest só escreve uma linha de JSON (ou uma mensagem de erro)
+const MAX_OUTPUT: usize = 64 * 1024;
+
+fn other(context: &str, e: impl std::fmt::Display) -> io::Error {
+    io::Error::other(format!("{}: {}", context, e))
+}
+
+pub struct WasmRunner {
+    engine: Engine,
+    pre: InstancePre<WasiP1Ctx>,
+}
+
+impl WasmRunner {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let engine = Engine::default();
+        let module = Module::from_file(&engine, path)
+            .map_err(|e| other(&format!("módulo {}", path.display()), e))?;
+
+        let mut linker: Linker<WasiP1Ctx> = Linker::new(&engine);
+        p1::add_to_linker_sync(&mut linker, |ctx| ctx).map_err(|e| other("linker WASI", e))?;
+        let pre = linker
+            .instantiate_pre(&module)
+            .map_err(|e| other("instanciação", e))?;
+
+        Ok(WasmRunner { engine, pre })
+    }
+
+    // WASM_MODULE ou o caminho padrão do build release
+    pub fn from_env() -> io::Result<Self> {
+        let path = std::env::var_os(MODULE_ENV).unwrap_or_else(|| DEFAULT_MODULE.into());
+        Self::load(Path::new(&path))
+    }
+
+    // Roda `core-algo-wasi <kernel> <size> <warmup> <reps> <seed> [precision [cutoff]]` numa instância nova
+    // (bloqueia: chamar via web::block). O aquecimento acontece dentro da mesma instância
+    pub fn run(&self, kernel: &str, size: usize, config: BenchConfig) -> io::Result<BenchResult> {
+        let stdout = MemoryOutputPipe::new(MAX_OUTPUT);
+        let stderr = MemoryOutputPipe::new(MAX_OUTPUT);
+        let mut args = vec![
+            "core-algo-wasi".to_string(),
+            kernel.to_string(),
+            size.to_string(),
+            config.warmup.to_string(),
+            config.reps.to_string(),
+            config.seed.to_string(),
+        ];
+        // Posicionais: com cutoff a precisão vai junto (f64, o padrão das matmul, se ninguém escolheu)
+        match config.cutoff {
+            Some(cutoff) => args.extend([config.precision.unwrap_or(Precision::F64).to_string(), cutoff.to_string()]),
+            None => args.extend(config.precision.map(|p| p.to_string())),
+        }
+        let wasi = WasiCtxBuilder::new()
+            .args(&args)
+            .stdout(stdout.clone())
+            .stderr(stderr.clone())
+            .build_p1();
+
+        let mut store = Store::new(&self.engine, wasi);
+        let instance = self.pre.instantiate(&mut store).map_err(|e| other("instância", e))?;
+        let start = instance
+            .get_typed_func::<(), ()>(&mut store, "_start")
+            .map_err(|e| other("_start", e))?;
+
+        // Um comando WASI que termina com exit(0) também chega aqui como "erro"
+        let code = match start.call(&mut store, ()) {
+            Ok(()) => 0,
+            Err(e) => match e.downcast_ref::<I32Exit>() {
+                Some(exit) => exit.0,
+                None => return Err(other("execução", e)),
+            },
+        };
+        drop(store);
+
+        if code != 0 {
+            let message = String::from_utf8_lossy(&stderr.contents()).trim().to_string();
+            return Err(other(&format!("core-algo-wasi saiu com {}", code), message));
+        }
+        serde_json::from_slice(&stdout.contents()).map_err(|e| other("saída do wasm", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_algo::{bench_kernel, verify, Mode};
+    use std::path::PathBuf;
+    use std::sync::OnceLock;
+
+    // O build WASI do workspace, compilado uma vez para os dois testes. Eles dependem do
+    // build (BUILD_HINT), então ficam #[ignore]: cargo test -p backend -- --ignored
+    fn runner() -> &'static WasmRunner {
+        static RUNNER: OnceLock<WasmRunner> = OnceLock::new();
+        RUNNER.get_or_init(|| {
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join(DEFAULT_MODULE);
+            assert!(path.exists(), "sem o build WASI em {}: rode {}", path.display(), BUILD_HINT);
+            WasmRunner::load(&path).unwrap()
+        })
+    }
+
+    #[test]
+    #[ignore = "precisa do build WASI: cargo build -p core-algo --bin core-algo-wasi --release --target wasm32-wasip1"]
+    fn guest_matches_native_checksum() {
+        let runner = runner();
+        let config = BenchConfig::new(Some(0), Some(1), Some(7)).unwrap();
+        for (kernel, size) in [("sieve", 10_000), ("matmul-tiled", 48)] {
+            let wasm = runner.run(kernel, size, config).unwrap();
+            let native = bench_kernel(kernel, size, Mode::Single, config).unwrap();
+            assert_eq!((wasm.target.as_str(), wasm.seed), ("wasm32-wasi", 7));
+            assert!(verify(&[native, wasm]).unwrap().ok, "{}", kernel);
+        }
+    }
+
+    #[test]
+    #[ignore = "precisa do build WASI: cargo build -p core-algo --bin core-algo-wasi --release --target wasm32-wasip1"]
+    fn guest_exit_code_becomes_error() {
+        let runner = runner();
+        let config = BenchConfig::default();
+        let error = runner.run("nao-existe", 10, config).unwrap_err().to_string();
+        assert!(error.starts_with("core-algo-wasi saiu com"), "{}", error);
+        assert!(error.contains("nao-existe"), "{}", error);
+    }
+}
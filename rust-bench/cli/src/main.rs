@@ -0,0 +1,285 @@
+// --- BENCH-CLI ---
+// O mesmo harness do backend (aquecimento + repetições), direto no terminal:
+//   bench-cli [opções] <kernel[:size]>...
+// Saída em tabela (padrão), JSON (lista de BenchResult) ou CSV. Progresso vai para o stderr,
+// então `--format json > resultados.json` sai limpo. Com matmul na lista, o matmul-blas roda
+// junto em cada size como referência (coluna "% BLAS" da tabela).
+use core_algo::{bench_kernel, bench_with_threads, catalog, check_threads, kernels, BenchConfig, BenchResult, Mode, Precision};
+use std::process::ExitCode;
+
+mod output;
+
+use output::Format;
+
+#[global_allocator]
+static ALLOC: core_algo::CountingAlloc = core_algo::CountingAlloc;
+
+const USAGE: &str = "\
+uso: bench-cli [opções] <kernel[:size]>...
+
+  kernel      nome do kernel (veja --list) ou `all`; `:size` troca o size só dele
+  --size N    size de quem não tem `:size` (padrão: o default de cada kernel)
+  --threads L lista de threads, ex. 1,2,4 (um pool para cada; padrão: todos os núcleos)
+  --single    uma thread só, sem rayon (Mode::Single)
+  --warmup N  execuções sem medir antes das repetições (padrão 1)
+  --reps N    repetições medidas (padrão 5)
+  --seed N    seed das entradas (padrão 42)
+  --precision P  f32 | f64 | i32 | i64 nas matmul
+  --cutoff N  lado em que matmul-recursive/matmul-strassen param de dividir (padrão 64)
+              (com `all`, --precision e --cutoff ficam só nos kernels que aceitam)
+  --format F  table | json | csv (padrão table)
+              (matmul na lista puxa o matmul-blas no mesmo size, como referência)
+  --list      lista os kernels e sai";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Threads {
+    // Pool global do rayon
+    All,
+    Single,
+    Pool(usize),
+}
+
+impl std::fmt::Display for Threads {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Threads::All => write!(f, "todos os núcleos"),
+            Threads::Single => write!(f, "uma thread"),
+            Threads::Pool(n) => write!(f, "pool de {}", n),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    runs: Vec<(&'static str, usize)>,
+    threads: Vec<Threads>,
+    config: BenchConfig,
+    format: Format,
+}
+
+enum Command {
+    Run(Args),
+    List,
+    Help,
+}
+
+fn number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("{}: número inválido '{}'", flag, value))
+}
+
+fn parse(args: &[String]) -> Result<Command, String> {
+    let mut names = Vec::new();
+    let (mut size, mut warmup, mut reps, mut seed) = (None, None, None, None);
+    let (mut precision, mut cutoff) = (None, None);
+    let mut threads = vec![Threads::All];
+    let mut single = false;
+    let mut format = Format::Table;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or_else(|| format!("{} precisa de um valor", arg));
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--list" => return Ok(Command::List),
+            "--single" => single = true,
+            "--size" => size = Some(number::<usize>(arg, value()?)?),
+            "--warmup" => warmup = Some(number(arg, value()?)?),
+            "--reps" => reps = Some(number(arg, value()?)?),
+            "--seed" => seed = Some(number(arg, value()?)?),
+            "--precision" => precision = Some(value()?.parse::<Precision>()?),
+            "--cutoff" => cutoff = Some(number(arg, value()?)?),
+            "--threads" => {
+                threads = value()?
+                    .split(',')
+                    .map(|t| {
+                        // Falha aqui, antes de rodar qualquer kernel da lista
+                        let n = number(arg, t.trim())?;
+                        check_threads(n).map(|_| Threads::Pool(n))
+                    })
+                    .collect::<Result<_, _>>()?;
+            }
+            "--format" => format = value()?.parse()?,
+            flag if flag.starts_with('-') => return Err(format!("opção desconhecida: {}", flag)),
+            name => names.push(name),
+        }
+    }
+    if names.is_empty() {
+        return Err("nenhum kernel (veja --list)".to_string());
+    }
+    if single {
+        if threads != [Threads::All] {
+            return Err("--single e --threads não combinam".to_string());
+        }
+        threads = vec![Threads::Single];
+    }
+
+    let config = BenchConfig {
+        precision,
+        cutoff,
+        ..BenchConfig::new(warmup, reps, seed)?
+    };
+    let mut runs = Vec::new();
+    for name in names {
+        let (name, own_size) = match name.split_once(':') {
+            Some((name, size)) => (name, Some(number::<usize>(name, size)?)),
+            None => (name, None),
+        };
+        let selected: Vec<_> = if name == "all" {
+            kernels::KERNELS
+                .iter()
+                .copied()
+                .filter(|k| kernels::resolve(k.name(), &config).is_ok())
+                .collect()
+        } else {
+            kernels::find(name).ok_or_else(|| format!("kernel desconhecido: {} (veja --list)", name))?;
+            vec![kernels::resolve(name, &config)?]
+        };
+        runs.extend(
+            selected
+                .into_iter()
+                .map(|k| (k.name(), own_size.or(size).unwrap_or_else(|| k.default_size()))),
+        );
+    }
+
+    Ok(Command::Run(Args {
+        runs,
+        threads,
+        config,
+        format,
+    }))
+}
+
+// Um matmul-blas por size das matmul pedidas que ainda não tenha o seu. Fica de fora quando
+// a precisão não existe no GEMM (inteiros); o --cutoff não vale para ele
+fn with_blas_reference(mut runs: Vec<(&'static str, usize)>, config: BenchConfig) -> Vec<(&'static str, usize)> {
+    let config = BenchConfig { cutoff: None, ..config };
+    let Ok(blas) = kernels::resolve(kernels::BLAS_KERNEL, &config) else {
+        return runs;
+    };
+    let sizes: Vec<usize> = runs
+        .iter()
+        .filter(|(name, _)| name.starts_with("matmul"))
+        .map(|&(_, size)| size)
+        .collect();
+    for size in sizes {
+        if !runs.contains(&(blas.name(), size)) {
+            runs.push((blas.name(), size));
+        }
+    }
+    runs
+}
+
+fn run(kernel: &str, size: usize, threads: Threads, config: BenchConfig) -> Result<BenchResult, String> {
+    let result = match threads {
+        Threads::All => Ok(bench_kernel(kernel, size, Mode::Parallel, config)),
+        Threads::Single => Ok(bench_kernel(kernel, size, Mode::Single, config)),
+        Threads::Pool(n) => bench_with_threads(kernel, size, config, n),
+    };
+    // O nome já foi conferido no parse
+    result.map(|r| r.expect("kernel do registro"))
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args = match parse(&args) {
+        Ok(Command::Run(args)) => args,
+        Ok(Command::List) => {
+            for k in catalog() {
+                println!("{:<18} size padrão {:>10}  {}", k.name, k.default_size, k.description);
+            }
+            return ExitCode::SUCCESS;
+        }
+        Ok(Command::Help) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut results = Vec::new();
+    for (kernel, size) in with_blas_reference(args.runs, args.config) {
+        let config = if kernel == kernels::BLAS_KERNEL {
+            BenchConfig { cutoff: None, ..args.config }
+        } else {
+            args.config
+        };
+        for &threads in &args.threads {
+            eprintln!("{} (size {}, {})...", kernel, size, threads);
+            match run(kernel, size, threads, config) {
+                Ok(result) => results.push(result),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+    }
+
+    print!("{}", args.format.render(&results));
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Result<Args, String> {
+        let words: Vec<String> = line.split_whitespace().map(String::from).collect();
+        match parse(&words)? {
+            Command::Run(args) => Ok(args),
+            _ => Err("não era uma execução".to_string()),
+        }
+    }
+
+    #[test]
+    fn parses_kernels_sizes_and_threads() {
+        let parsed = args("matmul-tiled:800 sieve --size 1000 --threads 1,4 --reps 3 --format csv").unwrap();
+        assert_eq!(parsed.runs, [("matmul-tiled", 800), ("sieve", 1000)]);
+        assert_eq!(parsed.threads, [Threads::Pool(1), Threads::Pool(4)]);
+        assert_eq!((parsed.config.warmup, parsed.config.reps), (1, 3));
+        assert_eq!(parsed.format, Format::Csv);
+
+        let all = args("all --single").unwrap();
+        assert_eq!(all.runs.len(), kernels::KERNELS.len());
+        assert_eq!(all.runs[0], ("matmul", 400));
+        assert_eq!(all.threads, [Threads::Single]);
+
+        assert!(args("").is_err());
+        assert!(args("nao-existe").is_err());
+        assert!(args("sieve --reps").is_err());
+        assert!(args("sieve --reps 0").is_err());
+        assert!(args("sieve --format xml").is_err());
+        assert!(args("sieve --single --threads 2").is_err());
+        assert!(args("sieve --threads 2,0").is_err());
+        assert!(args("sieve --threads 257").is_err());
+
+        let f32 = args("all --precision f32").unwrap();
+        assert_eq!(f32.config.precision, Some(Precision::F32));
+        assert!(f32.runs.iter().all(|(name, _)| name.starts_with("matmul")));
+        assert!(args("matmul-tiled --precision i64").is_ok());
+        assert!(args("sieve --precision f32").is_err());
+        assert!(args("matmul --precision f16").is_err());
+
+        let strassen = args("all --cutoff 32").unwrap();
+        assert_eq!(strassen.runs, [("matmul-recursive", 512), ("matmul-strassen", 512)]);
+        assert_eq!(strassen.config.cutoff, Some(32));
+        assert!(args("matmul-strassen --cutoff 4").is_err());
+        assert!(args("sieve --cutoff 32").is_err());
+    }
+
+    #[test]
+    fn adds_blas_reference_per_matmul_size() {
+        let parsed = args("matmul-tiled:800 matmul-strassen:800 matmul-blas:400 matmul sieve --size 400").unwrap();
+        let runs = with_blas_reference(parsed.runs, parsed.config);
+        assert_eq!(&runs[5..], [(kernels::BLAS_KERNEL, 800)]);
+
+        let strassen = args("matmul-strassen:256 --cutoff 32").unwrap();
+        assert_eq!(with_blas_reference(strassen.runs, strassen.config)[1], (kernels::BLAS_KERNEL, 256));
+        let integers = args("matmul-tiled --precision i32").unwrap();
+        assert_eq!(with_blas_reference(integers.runs, integers.config).len(), 1);
+        assert_eq!(with_blas_reference(args("sieve").unwrap().runs, BenchConfig::default()).len(), 1);
+    }
+}
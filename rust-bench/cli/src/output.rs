@@ -0,0 +1,161 @@
+// Formatos de saída do bench-cli. Todos levam as estatísticas do harness (sempre presentes aqui)
+use core_algo::{kernels::BLAS_KERNEL, BenchResult, Stats};
+use std::fmt::Write;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    // Colunas alinhadas, para ler no terminal
+    Table,
+    // Lista de BenchResult, o mesmo JSON do /api/native
+    Json,
+    // Uma linha por execução, para planilha/CI
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("formato desconhecido: {} (table, json ou csv)", s)),
+        }
+    }
+}
+
+const CSV_HEADER: &str = "kernel,size,precision,cutoff,seed,target,threads,warmup,reps,median_ms,min_ms,mean_ms,stddev_ms,p95_ms,ci95_low_ms,ci95_high_ms,gflops,checksum,rel_error,ops,peak_bytes,allocations";
+
+fn stats(result: &BenchResult) -> &Stats {
+    result.stats.as_ref().expect("resultado do harness")
+}
+
+// "-" quando o resultado não trouxe o pico de memória
+fn peak_mib(result: &BenchResult) -> String {
+    result
+        .memory
+        .as_ref()
+        .and_then(|m| m.peak_bytes)
+        .map_or("-".to_string(), |peak| format!("{:.1}", peak as f64 / (1024.0 * 1024.0)))
+}
+
+// Mediana do matmul-blas do mesmo lote (size, precisão, threads) / mediana deste resultado,
+// em %; "-" fora das matmul ou sem a referência
+fn blas_percent(result: &BenchResult, results: &[BenchResult]) -> String {
+    if !result.kernel.starts_with("matmul") || result.kernel == BLAS_KERNEL {
+        return "-".to_string();
+    }
+    results
+        .iter()
+        .find(|r| {
+            r.kernel == BLAS_KERNEL
+                && (r.size, r.precision, r.threads, &r.target) == (result.size, result.precision, result.threads, &result.target)
+        })
+        .map_or("-".to_string(), |blas| format!("{:.1}", 100.0 * stats(blas).median / stats(result).median))
+}
+
+impl Format {
+    pub fn render(self, results: &[BenchResult]) -> String {
+        match self {
+            Format::Json => serde_json::to_string_pretty(results).expect("BenchResult serializável") + "\n",
+            Format::Csv => {
+                let mut out = format!("{}\n", CSV_HEADER);
+                for r in results {
+                    let s = stats(r);
+                    let (peak, allocations) = r.memory.as_ref().map_or((String::new(), String::new()), |m| {
+                        (m.peak_bytes.map_or(String::new(), |p| p.to_string()), m.allocations.to_string())
+                    });
+                    let precision = r.precision.map_or("", |p| p.name());
+                    let cutoff = r.cutoff.map_or(String::new(), |c| c.to_string());
+                    let rel_error = r.numeric_error.map_or(String::new(), |e| e.relative.to_string());
+                    // Nomes de kernel/target não têm vírgula nem aspas: sem escape
+                    writeln!(
+                        out,
+                        "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                        r.kernel, r.size, precision, cutoff, r.seed, r.target, r.threads, s.warmup, s.reps, s.median, s.min,
+                        s.mean, s.stddev, s.p95, s.ci95_low, s.ci95_high, r.gflops, r.checksum, rel_error, r.ops, peak,
+                        allocations
+                    )
+                    .unwrap();
+                }
+                out
+            }
+            Format::Table => {
+                let mut out = format!(
+                    "{:<18} {:>10} {:<4} {:>7} {:>12} {:>10} {:>10} {:>9} {:>9} {:>7} {:>9}  {}\n",
+                    "kernel",
+                    "size",
+                    "tipo",
+                    "threads",
+                    "mediana ms",
+                    "min ms",
+                    "p95 ms",
+                    "±IC95",
+                    "G-ops/s",
+                    "% BLAS",
+                    "pico MiB",
+                    "checksum"
+                );
+                for r in results {
+                    let s = stats(r);
+                    writeln!(
+                        out,
+                        "{:<18} {:>10} {:<4} {:>7} {:>12.2} {:>10.2} {:>10.2} {:>9.2} {:>9} {:>7} {:>9}  {}",
+                        r.kernel,
+                        r.size,
+                        r.precision.map_or("-", |p| p.name()),
+                        r.threads,
+                        s.median,
+                        s.min,
+                        s.p95,
+                        (s.ci95_high - s.ci95_low) / 2.0,
+                        format!("{:.2}", r.gflops),
+                        blas_percent(r, results),
+                        peak_mib(r),
+                        r.checksum
+                    )
+                    .unwrap();
+                }
+                out
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_algo::{bench_kernel, BenchConfig, Mode};
+
+    #[test]
+    fn csv_has_one_row_per_result() {
+        let config = BenchConfig::new(Some(0), Some(2), None).unwrap();
+        let result = bench_kernel("sieve", 1000, Mode::Single, config).unwrap();
+        let csv = Format::Csv.render(&[result.clone(), result]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        let columns = CSV_HEADER.split(',').count();
+        assert!(lines.iter().all(|l| l.split(',').count() == columns));
+        // Sem escolha de precisão/cutoff (e sem erro numérico) as colunas ficam vazias
+        assert!(lines[1].starts_with("sieve,1000,,,42,native,1,0,2,"));
+        // O main.rs instala o CountingAlloc, então o binário de testes também conta
+        assert!(lines[1].contains(",168,,1000,"));
+        assert!(!lines[1].ends_with(','));
+    }
+
+    #[test]
+    fn table_shows_fraction_of_blas() {
+        let config = BenchConfig::new(Some(0), Some(1), None).unwrap();
+        let tiled = bench_kernel("matmul-tiled", 64, Mode::Single, config).unwrap();
+        let mut blas = bench_kernel(BLAS_KERNEL, 64, Mode::Single, config).unwrap();
+        blas.stats.as_mut().unwrap().median = stats(&tiled).median / 4.0;
+        let table = Format::Table.render(&[tiled.clone(), blas]);
+        let row = |kernel: &str| table.lines().find(|l| l.starts_with(kernel)).unwrap().to_string();
+        assert!(row("matmul-tiled ").contains(" 25.0 "), "{}", table);
+        assert!(row(BLAS_KERNEL).contains(" - "));
+        // Sem matmul-blas no lote, nem a matmul tem a coluna
+        assert!(!Format::Table.render(&[tiled]).contains(" 25.0 "));
+    }
+}
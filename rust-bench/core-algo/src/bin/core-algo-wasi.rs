@@ -0,0 +1,59 @@
+// Build WASI do core-algo, carregado pelo backend com wasmtime (/api/wasm):
+//   cargo build -p core-algo --bin core-algo-wasi --release --target wasm32-wasip1
+// Uso: core-algo-wasi <kernel> <size> [warmup reps [seed [precision [cutoff]]]]
+//   -> uma linha de JSON (BenchResult) no stdout
+use core_algo::{bench_kernel, kernels, BenchConfig, Mode, Precision};
+use std::process::ExitCode;
+
+#[global_allocator]
+static ALLOC: core_algo::CountingAlloc = core_algo::CountingAlloc;
+
+fn parse(args: &[String]) -> Result<(&str, usize, BenchConfig), String> {
+    let number = |s: &String| s.parse::<u64>().map_err(|_| format!("número inválido: {}", s));
+    let config = |warmup, reps, seed: Option<&String>, precision: Option<&String>, cutoff: Option<&String>| -> Result<BenchConfig, String> {
+        let mut config = BenchConfig::new(
+            Some(number(warmup)? as usize),
+            Some(number(reps)? as usize),
+            seed.map(number).transpose()?,
+        )?;
+        config.precision = precision.map(|p| p.parse::<Precision>()).transpose()?;
+        config.cutoff = cutoff.map(|c| number(c).map(|c| c as usize)).transpose()?;
+        Ok(config)
+    };
+    match args {
+        [kernel, size] => Ok((kernel, number(size)? as usize, BenchConfig::default())),
+        [kernel, size, warmup, reps] => Ok((kernel, number(size)? as usize, config(warmup, reps, None, None, None)?)),
+        [kernel, size, warmup, reps, seed] => {
+            Ok((kernel, number(size)? as usize, config(warmup, reps, Some(seed), None, None)?))
+        }
+        [kernel, size, warmup, reps, seed, precision] => {
+            Ok((kernel, number(size)? as usize, config(warmup, reps, Some(seed), Some(precision), None)?))
+        }
+        [kernel, size, warmup, reps, seed, precision, cutoff] => Ok((
+            kernel,
+            number(size)? as usize,
+            config(warmup, reps, Some(seed), Some(precision), Some(cutoff))?,
+        )),
+        _ => Err("uso: core-algo-wasi <kernel> <size> [warmup reps [seed [precision [cutoff]]]]".to_string()),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (kernel, size, config) = match parse(&args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    if let Err(e) = kernels::resolve(kernel, &config) {
+        eprintln!("{}", e);
+        return ExitCode::from(2);
+    }
+    // No WASI não há threads: Mode::default() é Single
+    let result = bench_kernel(kernel, size, Mode::default(), config).expect("kernel resolvido");
+    println!("{}", serde_json::to_string(&result).expect("BenchResult serializável"));
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,48 @@
+// Relógio portátil para medir o tempo dentro do próprio módulo:
+//   nativo e WASI (wasm32-wasip1) -> std::time::Instant (no WASI vira clock_time_get)
+//   wasm32 no browser / Node      -> performance.now() (global nos dois)
+// Assim o tempo de cálculo sai igual nos dois alvos, sem o overhead da cola JS.
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+mod imp {
+    use wasm_bindgen::prelude::*;
+
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_namespace = performance, js_name = now)]
+        fn performance_now() -> f64;
+    }
+
+    #[derive(Clone, Copy)]
+    pub struct Stopwatch(f64);
+
+    impl Stopwatch {
+        pub fn start() -> Self {
+            Stopwatch(performance_now())
+        }
+
+        pub fn elapsed_ms(&self) -> f64 {
+            performance_now() - self.0
+        }
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+mod imp {
+    use std::time::Instant;
+
+    #[derive(Clone, Copy)]
+    pub struct Stopwatch(Instant);
+
+    impl Stopwatch {
+        pub fn start() -> Self {
+            Stopwatch(Instant::now())
+        }
+
+        pub fn elapsed_ms(&self) -> f64 {
+            self.0.elapsed().as_secs_f64() * 1000.0
+        }
+    }
+}
+
+pub use imp::Stopwatch;
@@ -0,0 +1,121 @@
+// --- PROGRESSO E CANCELAMENTO ---
+// Execuções longas (matmul 4000x4000) precisam avisar quanto já andaram e parar
+// quando alguém cancela. O kernel recebe um `Control`, cria um `Progress` com o total
+// de unidades (linhas, segmentos, estágios...) e chama `advance` entre uma e outra.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+// O kernel parou porque o token foi cancelado
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "execução cancelada")
+    }
+}
+
+// Compartilhado entre quem roda (kernel) e quem cancela (ex.: DELETE /api/jobs/{id})
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+// Fração concluída (0.0..=1.0); chamado de várias threads ao mesmo tempo no modo paralelo
+pub type ProgressFn<'a> = &'a (dyn Fn(f64) + Sync);
+
+#[derive(Clone, Copy, Default)]
+pub struct Control<'a> {
+    cancel: Option<&'a CancelToken>,
+    progress: Option<ProgressFn<'a>>,
+}
+
+impl<'a> Control<'a> {
+    // Sem progresso e sem cancelamento (o caso de run_kernel / bench_kernel)
+    pub const NONE: Control<'static> = Control {
+        cancel: None,
+        progress: None,
+    };
+
+    pub fn new(cancel: Option<&'a CancelToken>, progress: Option<ProgressFn<'a>>) -> Self {
+        Control { cancel, progress }
+    }
+
+    pub fn cancel_token(&self) -> Option<&'a CancelToken> {
+        self.cancel
+    }
+
+    pub fn check(&self) -> Result<(), Cancelled> {
+        match self.cancel {
+            Some(token) if token.is_cancelled() => Err(Cancelled),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn report(&self, fraction: f64) {
+        if let Some(progress) = self.progress {
+            progress(fraction.clamp(0.0, 1.0));
+        }
+    }
+
+    // Contador de `total` unidades de trabalho deste kernel
+    pub fn progress(&self, total: usize) -> Progress<'_> {
+        Progress {
+            control: self,
+            total: total.max(1),
+            done: AtomicUsize::new(0),
+        }
+    }
+}
+
+pub struct Progress<'c> {
+    control: &'c Control<'c>,
+    total: usize,
+    done: AtomicUsize,
+}
+
+impl Progress<'_> {
+    // Mais `units` prontas; Err(Cancelled) se é hora de parar
+    pub fn advance(&self, units: usize) -> Result<(), Cancelled> {
+        let done = self.done.fetch_add(units, Ordering::Relaxed) + units;
+        self.control.report(done as f64 / self.total as f64);
+        self.control.check()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn reports_and_stops_when_cancelled() {
+        let token = CancelToken::new();
+        let seen = Mutex::new(Vec::new());
+        let report = |f: f64| seen.lock().unwrap().push(f);
+        let control = Control::new(Some(&token), Some(&report));
+
+        let progress = control.progress(4);
+        assert!(progress.advance(1).is_ok());
+        assert!(progress.advance(1).is_ok());
+        token.cancel();
+        assert_eq!(progress.advance(1), Err(Cancelled));
+        assert_eq!(*seen.lock().unwrap(), [0.25, 0.5, 0.75]);
+
+        assert!(Control::NONE.progress(1).advance(1).is_ok());
+    }
+}
@@ -0,0 +1,249 @@
+// --- HARNESS: AQUECIMENTO + REPETIÇÕES ---
+// Uma execução fria só (heavy_computation / run_kernel) varia muito de uma vez para outra:
+// cache frio, JIT do wasm, frequência da CPU subindo... Aqui o kernel roda `warmup` vezes
+// sem medir e depois `reps` vezes medindo, e o resultado leva as estatísticas das amostras.
+
+use crate::control::{Cancelled, Control};
+use crate::kernels::{self, Mode, Precision, DEFAULT_SEED};
+use crate::memory::MemoryProbe;
+use crate::{BenchResult, Stopwatch};
+use serde::{Deserialize, Serialize};
+
+// Limite de repetições aceito por quem expõe o harness (API, wasm)
+pub const MAX_REPS: usize = 100;
+pub const MAX_WARMUP: usize = 20;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct BenchConfig {
+    pub warmup: usize,
+    pub reps: usize,
+    pub seed: u64,
+    // Tipo do elemento nos kernels que têm essa escolha (None: o padrão do kernel, f64)
+    #[serde(default)]
+    pub precision: Option<Precision>,
+    // Lado em que as matmul recursivas param de dividir (None: strassen::DEFAULT_CUTOFF)
+    #[serde(default)]
+    pub cutoff: Option<usize>,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            warmup: 1,
+            reps: 5,
+            seed: DEFAULT_SEED,
+            precision: None,
+            cutoff: None,
+        }
+    }
+}
+
+impl BenchConfig {
+    // Campos ausentes ficam com o padrão; fora dos limites vira erro (mensagem para o usuário)
+    pub fn new(warmup: Option<usize>, reps: Option<usize>, seed: Option<u64>) -> Result<Self, String> {
+        let default = BenchConfig::default();
+        let config = BenchConfig {
+            warmup: warmup.unwrap_or(default.warmup),
+            reps: reps.unwrap_or(default.reps),
+            seed: seed.unwrap_or(default.seed),
+            precision: None,
+            cutoff: None,
+        };
+        if config.reps == 0 || config.reps > MAX_REPS {
+            return Err(format!("reps deve estar entre 1 e {}", MAX_REPS));
+        }
+        if config.warmup > MAX_WARMUP {
+            return Err(format!("warmup deve ser no máximo {}", MAX_WARMUP));
+        }
+        Ok(config)
+    }
+}
+
+// Estatísticas dos tempos medidos (ms)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct Stats {
+    pub warmup: usize,
+    pub reps: usize,
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    // Desvio padrão amostral (n - 1)
+    pub stddev: f64,
+    pub p95: f64,
+    // Intervalo de confiança de 95% para a média (t de Student)
+    pub ci95_low: f64,
+    pub ci95_high: f64,
+    pub samples: Vec<f64>,
+}
+
+// t crítico bicaudal de 95% para 1..=30 graus de liberdade; acima disso ~normal
+const T95: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+    2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+    2.052, 2.048, 2.045, 2.042,
+];
+
+// Percentil com interpolação linear entre as amostras ordenadas
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+}
+
+impl Stats {
+    pub fn from_samples(warmup: usize, samples: Vec<f64>) -> Self {
+        assert!(!samples.is_empty(), "pelo menos uma amostra");
+        let n = samples.len();
+        let mut sorted = samples.clone();
+        sorted.sort_by(f64::total_cmp);
+
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let stddev = if n > 1 {
+            (samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64).sqrt()
+        } else {
+            0.0
+        };
+        let t = T95.get(n.saturating_sub(2)).copied().unwrap_or(1.96);
+        let half_width = if n > 1 { t * stddev / (n as f64).sqrt() } else { 0.0 };
+
+        Stats {
+            warmup,
+            reps: n,
+            min: sorted[0],
+            median: percentile(&sorted, 0.5),
+            mean,
+            stddev,
+            p95: percentile(&sorted, 0.95),
+            ci95_low: mean - half_width,
+            ci95_high: mean + half_width,
+            samples,
+        }
+    }
+}
+
+// Roda um kernel pelo nome com aquecimento e repetições. None se o nome não existe
+// (ou se o kernel não tem o `config.precision` / `config.cutoff` pedido; ver kernels::resolve).
+// elapsed_ms (e gflops) do resultado usam a mediana
+pub fn bench_kernel(name: &str, size: usize, mode: Mode, config: BenchConfig) -> Option<BenchResult> {
+    bench_kernel_with(name, size, mode, config, &Control::NONE).expect("sem token de cancelamento")
+}
+
+// `bench_kernel` com progresso e cancelamento: a fração reportada cobre aquecimento +
+// repetições (cada execução é 1/total do caminho)
+pub fn bench_kernel_with(
+    name: &str,
+    size: usize,
+    mode: Mode,
+    config: BenchConfig,
+    ctl: &Control,
+) -> Result<Option<BenchResult>, Cancelled> {
+    let Some(kernel) = kernels::find_as(name, config.precision) else {
+        return Ok(None);
+    };
+    let tuned = match config.cutoff.map(|cutoff| kernel.with_cutoff(cutoff)) {
+        Some(None) => return Ok(None),
+        tuned => tuned.flatten(),
+    };
+    let kernel = tuned.as_deref().unwrap_or(kernel);
+    let reps = config.reps.max(1);
+    let runs = (config.warmup + reps) as f64;
+    let run = |index: usize| {
+        let report = |fraction: f64| ctl.report((index as f64 + fraction) / runs);
+        let ctl = Control::new(ctl.cancel_token(), Some(&report));
+        match mode {
+            Mode::Single => kernel.run_single(size, config.seed, &ctl),
+            Mode::Parallel => kernel.run_parallel(size, config.seed, &ctl),
+        }
+    };
+
+    // A memória cobre o aquecimento também: é nele que o wasm cresce a memória linear
+    let probe = MemoryProbe::start();
+    for index in 0..config.warmup {
+        run(index)?;
+    }
+
+    let mut samples = Vec::with_capacity(reps);
+    let mut output = None;
+    for index in config.warmup..config.warmup + reps {
+        let start = Stopwatch::start();
+        output = Some(run(index)?);
+        samples.push(start.elapsed_ms());
+    }
+    let output = output.expect("pelo menos uma repetição");
+    let memory = probe.finish(config.warmup + reps);
+    let stats = Stats::from_samples(config.warmup, samples);
+    // Fora do tempo e da memória medidos; sem progresso (a fração já chegou em 1)
+    let numeric_error = kernel.numeric_error(size, config.seed, mode, &Control::new(ctl.cancel_token(), None))?;
+
+    let mut result = BenchResult::new(
+        kernel.name(),
+        size,
+        config.seed,
+        stats.median,
+        kernels::threads_for(mode),
+        output.checksum,
+        output.ops,
+    );
+    result.stats = Some(stats);
+    result.precision = kernel.precision();
+    result.cutoff = kernel.cutoff();
+    result.numeric_error = numeric_error;
+    result.memory = memory;
+    Ok(Some(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_of_known_samples() {
+        let stats = Stats::from_samples(0, vec![4.0, 1.0, 3.0, 2.0, 5.0]);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.median, 3.0);
+        assert_eq!(stats.mean, 3.0);
+        assert!((stats.stddev - 2.5f64.sqrt()).abs() < 1e-12);
+        assert!((stats.p95 - 4.8).abs() < 1e-12);
+        // t(4) = 2.776
+        let half = 2.776 * 2.5f64.sqrt() / 5f64.sqrt();
+        assert!((stats.ci95_high - 3.0 - half).abs() < 1e-12);
+        assert!((3.0 - stats.ci95_low - half).abs() < 1e-12);
+
+        let single = Stats::from_samples(0, vec![7.0]);
+        assert_eq!((single.median, single.stddev, single.ci95_low, single.p95), (7.0, 0.0, 7.0, 7.0));
+    }
+
+    #[test]
+    fn bench_kernel_collects_reps() {
+        let config = BenchConfig::new(Some(2), Some(4), None).unwrap();
+        let result = bench_kernel("sieve", 1000, Mode::Single, config).unwrap();
+        let stats = result.stats.as_ref().unwrap();
+        assert_eq!((stats.warmup, stats.reps, stats.samples.len()), (2, 4, 4));
+        assert_eq!(result.elapsed_ms, stats.median);
+        assert_eq!(result.checksum, 168.0);
+
+        assert!(BenchConfig::new(None, Some(0), None).is_err());
+        assert!(BenchConfig::new(None, Some(MAX_REPS + 1), None).is_err());
+        assert!(bench_kernel("nao-existe", 10, Mode::Single, config).is_none());
+        assert_eq!(result.precision, None);
+
+        let f32 = BenchConfig {
+            precision: Some(Precision::F32),
+            ..config
+        };
+        assert_eq!(bench_kernel("matmul-ikj", 16, Mode::Single, f32).unwrap().precision, Some(Precision::F32));
+        assert!(bench_kernel("sieve", 1000, Mode::Single, f32).is_none());
+        assert_eq!(result.numeric_error, None);
+
+        let cutoff = BenchConfig {
+            cutoff: Some(16),
+            ..config
+        };
+        let strassen = bench_kernel("matmul-strassen", 40, Mode::Parallel, cutoff).unwrap();
+        assert_eq!(strassen.cutoff, Some(16));
+        assert!(strassen.numeric_error.unwrap().relative < 1e-13);
+        assert!(bench_kernel("matmul-strassen", 40, Mode::Single, BenchConfig { cutoff: Some(2), ..config }).is_none());
+        assert!(bench_kernel("matmul-tiled", 40, Mode::Single, cutoff).is_none());
+    }
+}
@@ -0,0 +1,141 @@
+use super::{Kernel, Output};
+use crate::control::{Cancelled, Control};
+use rayon::prelude::*;
+use std::f64::consts::PI;
+
+// FFT radix-2 iterativa (Cooley-Tukey) sobre números complexos
+pub struct Fft;
+
+#[derive(Clone, Copy)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    fn mul(self, o: Complex) -> Complex {
+        Complex {
+            re: self.re * o.re - self.im * o.im,
+            im: self.re * o.im + self.im * o.re,
+        }
+    }
+}
+
+// Sinal de entrada: soma de senoides, com tamanho arredondado para potência de 2
+fn signal(size: usize) -> Vec<Complex> {
+    let n = size.max(2).next_power_of_two();
+    let mut data: Vec<Complex> = (0..n)
+        .map(|i| {
+            let t = i as f64;
+            Complex {
+                re: (t * 0.01).sin() + 0.5 * (t * 0.37).cos(),
+                im: 0.0,
+            }
+        })
+        .collect();
+
+    // Reordena por bit-reverso para a versão iterativa
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+    data
+}
+
+fn twiddles(n: usize) -> Vec<Complex> {
+    (0..n / 2)
+        .map(|k| {
+            let angle = -2.0 * PI * k as f64 / n as f64;
+            Complex {
+                re: angle.cos(),
+                im: angle.sin(),
+            }
+        })
+        .collect()
+}
+
+// Borboletas de um bloco de tamanho `len` de um estágio
+fn butterflies(block: &mut [Complex], twiddles: &[Complex], stride: usize) {
+    let half = block.len() / 2;
+    for k in 0..half {
+        let w = twiddles[k * stride];
+        let u = block[k];
+        let v = block[k + half].mul(w);
+        block[k] = Complex { re: u.re + v.re, im: u.im + v.im };
+        block[k + half] = Complex { re: u.re - v.re, im: u.im - v.im };
+    }
+}
+
+fn output(data: &[Complex]) -> Output {
+    let n = data.len() as u64;
+    Output {
+        checksum: data.iter().map(|c| (c.re * c.re + c.im * c.im).sqrt()).sum(),
+        // Estimativa clássica: 5 N log2 N operações de ponto flutuante
+        ops: 5 * n * n.trailing_zeros() as u64,
+    }
+}
+
+impl Kernel for Fft {
+    fn name(&self) -> &'static str {
+        "fft"
+    }
+
+    fn description(&self) -> &'static str {
+        "FFT complexa radix-2 (f64); size = pontos (arredondado para potência de 2)"
+    }
+
+    fn default_size(&self) -> usize {
+        1 << 20
+    }
+
+    fn max_size(&self) -> usize {
+        1 << 28
+    }
+
+    // Sinal (16 bytes por ponto, já arredondado) + N/2 twiddles
+    fn memory_bytes(&self, size: usize) -> u64 {
+        size.max(2).checked_next_power_of_two().map_or(u64::MAX, |n| n as u64 * 24)
+    }
+
+    fn run_single(&self, size: usize, _seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        let mut data = signal(size);
+        let n = data.len();
+        let twiddles = twiddles(n);
+        // Um passo de progresso por estágio (log2 N)
+        let progress = ctl.progress(n.trailing_zeros() as usize);
+
+        let mut len = 2;
+        while len <= n {
+            for block in data.chunks_mut(len) {
+                butterflies(block, &twiddles, n / len);
+            }
+            progress.advance(1)?;
+            len *= 2;
+        }
+
+        Ok(output(&data))
+    }
+
+    fn run_parallel(&self, size: usize, _seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        let mut data = signal(size);
+        let n = data.len();
+        let twiddles = twiddles(n);
+        let progress = ctl.progress(n.trailing_zeros() as usize);
+
+        // Cada estágio tem blocos independentes; nos últimos estágios sobram poucos
+        // blocos, então o paralelismo cai (limitação conhecida da versão iterativa)
+        let mut len = 2;
+        while len <= n {
+            data.par_chunks_mut(len).for_each(|block| {
+                butterflies(block, &twiddles, n / len);
+            });
+            progress.advance(1)?;
+            len *= 2;
+        }
+
+        Ok(output(&data))
+    }
+}
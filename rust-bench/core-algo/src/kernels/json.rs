@@ -0,0 +1,95 @@
+use super::{Kernel, Output};
+use crate::control::{Cancelled, Control};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Serializa e lê de volta registros em JSON (serde_json), em blocos
+pub struct Json;
+
+const CHUNK: usize = 1024;
+
+#[derive(Serialize, Deserialize)]
+struct Record {
+    id: u64,
+    name: String,
+    score: f64,
+    active: bool,
+    tags: Vec<String>,
+}
+
+fn record(i: usize) -> Record {
+    Record {
+        id: i as u64,
+        name: format!("usuario-{}", i),
+        score: (i % 1000) as f64 * 0.25,
+        active: i.is_multiple_of(3),
+        tags: (0..i % 4).map(|t| format!("tag{}", t)).collect(),
+    }
+}
+
+// Um bloco de registros: serializa, faz o parse de volta e devolve (soma dos scores, bytes)
+fn roundtrip(start: usize, end: usize) -> (f64, u64) {
+    let records: Vec<Record> = (start..end).map(record).collect();
+    let text = serde_json::to_string(&records).expect("registros serializáveis");
+    let parsed: Vec<Record> = serde_json::from_str(&text).expect("JSON gerado por nós mesmos");
+    (parsed.iter().map(|r| r.score).sum(), text.len() as u64)
+}
+
+fn chunks(size: usize) -> impl Iterator<Item = (usize, usize)> + Clone {
+    (0..size).step_by(CHUNK).map(move |start| (start, (start + CHUNK).min(size)))
+}
+
+fn output(parts: Vec<(f64, u64)>) -> Output {
+    Output {
+        checksum: parts.iter().map(|p| p.0).sum(),
+        // Bytes escritos + bytes lidos
+        ops: parts.iter().map(|p| p.1 * 2).sum(),
+    }
+}
+
+impl Kernel for Json {
+    fn name(&self) -> &'static str {
+        "json"
+    }
+
+    fn description(&self) -> &'static str {
+        "Serialização + parse JSON de registros; size = quantidade de registros (ops = bytes)"
+    }
+
+    fn default_size(&self) -> usize {
+        200_000
+    }
+
+    fn max_size(&self) -> usize {
+        20_000_000
+    }
+
+    // Um bloco por thread (registros + texto, ~256 bytes por registro) + o resumo de cada bloco
+    fn memory_bytes(&self, size: usize) -> u64 {
+        (CHUNK * 256 * rayon::current_num_threads() + size.div_ceil(CHUNK) * 16) as u64
+    }
+
+    fn run_single(&self, size: usize, _seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        let progress = ctl.progress(size.div_ceil(CHUNK));
+        let parts = chunks(size)
+            .map(|(s, e)| {
+                let part = roundtrip(s, e);
+                progress.advance(1).map(|_| part)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(output(parts))
+    }
+
+    fn run_parallel(&self, size: usize, _seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        let chunks: Vec<_> = chunks(size).collect();
+        let progress = ctl.progress(chunks.len());
+        let parts = chunks
+            .into_par_iter()
+            .map(|(s, e)| {
+                let part = roundtrip(s, e);
+                progress.advance(1).map(|_| part)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(output(parts))
+    }
+}
@@ -0,0 +1,76 @@
+use super::{Kernel, Output};
+use crate::control::{Cancelled, Control};
+use rayon::prelude::*;
+
+// Conjunto de Mandelbrot numa imagem NxN (trabalho desigual entre linhas)
+pub struct Mandelbrot;
+
+const MAX_ITER: u32 = 256;
+
+// Quantas iterações cada pixel da linha `y` levou para escapar
+fn row_iterations(y: usize, size: usize) -> u64 {
+    let ci = -1.5 + 3.0 * y as f64 / size as f64;
+    (0..size)
+        .map(|x| {
+            let cr = -2.0 + 3.0 * x as f64 / size as f64;
+            let (mut zr, mut zi) = (0.0f64, 0.0f64);
+            let mut iter = 0;
+            while iter < MAX_ITER && zr * zr + zi * zi <= 4.0 {
+                let tmp = zr * zr - zi * zi + cr;
+                zi = 2.0 * zr * zi + ci;
+                zr = tmp;
+                iter += 1;
+            }
+            iter as u64
+        })
+        .sum()
+}
+
+fn output(iterations: u64) -> Output {
+    Output {
+        checksum: iterations as f64,
+        // Cada iteração tem ~7 operações de ponto flutuante
+        ops: iterations * 7,
+    }
+}
+
+impl Kernel for Mandelbrot {
+    fn name(&self) -> &'static str {
+        "mandelbrot"
+    }
+
+    fn description(&self) -> &'static str {
+        "Mandelbrot NxN com até 256 iterações por pixel; size = N"
+    }
+
+    fn default_size(&self) -> usize {
+        1000
+    }
+
+    fn max_size(&self) -> usize {
+        20_000
+    }
+
+    // Não guarda a imagem, só soma as iterações
+    fn memory_bytes(&self, _size: usize) -> u64 {
+        0
+    }
+
+    fn run_single(&self, size: usize, _seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        let progress = ctl.progress(size);
+        let row = |y| {
+            let iterations = row_iterations(y, size);
+            progress.advance(1).map(|_| iterations)
+        };
+        Ok(output((0..size).map(row).sum::<Result<u64, _>>()?))
+    }
+
+    fn run_parallel(&self, size: usize, _seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        let progress = ctl.progress(size);
+        let row = |y| {
+            let iterations = row_iterations(y, size);
+            progress.advance(1).map(|_| iterations)
+        };
+        Ok(output((0..size).into_par_iter().map(row).sum::<Result<u64, _>>()?))
+    }
+}
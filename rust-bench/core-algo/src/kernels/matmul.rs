@@ -0,0 +1,380 @@
+use super::{Kernel, Mode, Output, Precision};
+use crate::control::{Cancelled, Control};
+use crate::matrix::{self, Element, MatMulVariant};
+use crate::strassen::{self, NumericError, Recursion};
+use rayon::prelude::*;
+
+// A multiplicação de matrizes original (Vec<Vec<f64>>, laço i-j-k ingênuo), em qualquer Element
+pub struct MatMul(pub Precision);
+
+// As mesmas entradas das variantes contíguas (e da GPU), copiadas para Vec<Vec<T>>
+fn inputs<T: Element>(size: usize, seed: u64) -> (Vec<Vec<T>>, Vec<Vec<T>>) {
+    let (a, b) = matrix::matmul_inputs_as::<T>(size, seed);
+    let rows = |m: &matrix::Matrix<T>| (0..size).map(|i| m.row(i).to_vec()).collect();
+    (rows(&a), rows(&b))
+}
+
+// Uma linha do resultado: row[j] = soma_k a[i][k] * b[k][j]
+fn compute_row<T: Element>(i: usize, row: &mut [T], a: &[Vec<T>], b: &[Vec<T>]) {
+    let size = row.len();
+    for j in 0..size {
+        for k in 0..size {
+            row[j] += a[i][k] * b[k][j];
+        }
+    }
+}
+
+fn output<T: Element>(result: &[Vec<T>], size: usize) -> Output {
+    let flat: Vec<T> = result.iter().flatten().copied().collect();
+    Output {
+        checksum: matrix::weighted_checksum(&flat),
+        ops: 2 * (size as u64).pow(3),
+    }
+}
+
+impl MatMul {
+    fn run_as<T: Element>(&self, size: usize, seed: u64, mode: Mode, ctl: &Control) -> Result<Output, Cancelled> {
+        let (matrix_a, matrix_b) = inputs::<T>(size, seed);
+        let mut result = vec![vec![T::default(); size]; size];
+        let progress = ctl.progress(size);
+
+        match mode {
+            Mode::Single => {
+                for (i, row) in result.iter_mut().enumerate() {
+                    compute_row(i, row, &matrix_a, &matrix_b);
+                    progress.advance(1)?;
+                }
+            }
+            // par_iter_mut() divide as linhas da matriz entre os núcleos da CPU automaticamente
+            Mode::Parallel => result.par_iter_mut().enumerate().try_for_each(|(i, row)| {
+                compute_row(i, row, &matrix_a, &matrix_b);
+                progress.advance(1)
+            })?,
+        }
+
+        Ok(output(&result, size))
+    }
+
+    fn run(&self, size: usize, seed: u64, mode: Mode, ctl: &Control) -> Result<Output, Cancelled> {
+        match self.0 {
+            Precision::F32 => self.run_as::<f32>(size, seed, mode, ctl),
+            Precision::F64 => self.run_as::<f64>(size, seed, mode, ctl),
+            Precision::I32 => self.run_as::<i32>(size, seed, mode, ctl),
+            Precision::I64 => self.run_as::<i64>(size, seed, mode, ctl),
+        }
+    }
+}
+
+impl Kernel for MatMul {
+    fn name(&self) -> &'static str {
+        "matmul"
+    }
+
+    fn description(&self) -> &'static str {
+        "Multiplicação de matrizes NxN (Vec<Vec>, i-j-k ingênuo); size = N"
+    }
+
+    fn default_size(&self) -> usize {
+        400
+    }
+
+    // O i-j-k ingênuo passa de minutos bem antes disso
+    fn max_size(&self) -> usize {
+        8192
+    }
+
+    // A e B contíguas + as cópias em Vec<Vec> (pico dentro de `inputs`); depois C
+    fn memory_bytes(&self, size: usize) -> u64 {
+        super::matrix_bytes(size, 4, self.0)
+    }
+
+    fn precision(&self) -> Option<Precision> {
+        Some(self.0)
+    }
+
+    fn run_single(&self, size: usize, seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        self.run(size, seed, Mode::Single, ctl)
+    }
+
+    fn run_parallel(&self, size: usize, seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        self.run(size, seed, Mode::Parallel, ctl)
+    }
+}
+
+// As mesmas contas em matriz contígua, com a ordem dos laços escolhida pela variante
+pub struct FlatMatMul(pub MatMulVariant, pub Precision);
+
+impl FlatMatMul {
+    fn run_as<T: Element>(&self, size: usize, seed: u64, mode: Mode, ctl: &Control) -> Result<Output, Cancelled> {
+        let (a, b) = matrix::matmul_inputs_as::<T>(size, seed);
+        let c = matrix::matmul_with(&a, &b, self.0, mode, ctl)?;
+        Ok(Output {
+            checksum: c.checksum(),
+            ops: 2 * (size as u64).pow(3),
+        })
+    }
+
+    fn run(&self, size: usize, seed: u64, mode: Mode, ctl: &Control) -> Result<Output, Cancelled> {
+        match self.1 {
+            Precision::F32 => self.run_as::<f32>(size, seed, mode, ctl),
+            Precision::F64 => self.run_as::<f64>(size, seed, mode, ctl),
+            Precision::I32 => self.run_as::<i32>(size, seed, mode, ctl),
+            Precision::I64 => self.run_as::<i64>(size, seed, mode, ctl),
+        }
+    }
+}
+
+impl Kernel for FlatMatMul {
+    fn name(&self) -> &'static str {
+        match self.0 {
+            MatMulVariant::Naive => "matmul-flat",
+            MatMulVariant::Ikj => "matmul-ikj",
+            MatMulVariant::Tiled => "matmul-tiled",
+            MatMulVariant::TransposedB => "matmul-transposed",
+            MatMulVariant::Simd => "matmul-simd",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self.0 {
+            MatMulVariant::Naive => "Matmul NxN em matriz contígua, i-j-k ingênuo; size = N",
+            MatMulVariant::Ikj => "Matmul NxN em matriz contígua, laço i-k-j (B lida por linha); size = N",
+            MatMulVariant::Tiled => "Matmul NxN em matriz contígua, i-k-j em blocos de 64x64; size = N",
+            MatMulVariant::TransposedB => "Matmul NxN em matriz contígua, B transposta antes (produto de linhas); size = N",
+            MatMulVariant::Simd => "Matmul NxN em blocos com SIMD explícito (AVX2/SSE2 ou simd128); size = N",
+        }
+    }
+
+    fn default_size(&self) -> usize {
+        400
+    }
+
+    fn max_size(&self) -> usize {
+        8192
+    }
+
+    // A, B e C (mais B transposta na variante transposed)
+    fn memory_bytes(&self, size: usize) -> u64 {
+        let matrices = if self.0 == MatMulVariant::TransposedB { 4 } else { 3 };
+        super::matrix_bytes(size, matrices, self.1)
+    }
+
+    fn precision(&self) -> Option<Precision> {
+        Some(self.1)
+    }
+
+    fn run_single(&self, size: usize, seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        self.run(size, seed, Mode::Single, ctl)
+    }
+
+    fn run_parallel(&self, size: usize, seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        self.run(size, seed, Mode::Parallel, ctl)
+    }
+}
+
+// Divide e conquista (8 produtos por nível) ou Strassen (7), com a variante Simd no cutoff
+#[derive(Clone, Copy)]
+pub struct RecursiveMatMul {
+    pub recursion: Recursion,
+    pub precision: Precision,
+    pub cutoff: usize,
+}
+
+impl RecursiveMatMul {
+    pub const fn new(recursion: Recursion, precision: Precision) -> Self {
+        RecursiveMatMul {
+            recursion,
+            precision,
+            cutoff: strassen::DEFAULT_CUTOFF,
+        }
+    }
+
+    fn multiply<T: Element>(&self, size: usize, seed: u64, mode: Mode, ctl: &Control) -> Result<matrix::Matrix<T>, Cancelled> {
+        let (a, b) = matrix::matmul_inputs_as::<T>(size, seed);
+        strassen::recursive_matmul_with(&a, &b, self.recursion, self.cutoff, mode, ctl)
+    }
+
+    // ops = 2N³ do O(n³) também no Strassen: o G-ops/s vira "efetivo", comparável com as outras matmul
+    fn run_as<T: Element>(&self, size: usize, seed: u64, mode: Mode, ctl: &Control) -> Result<Output, Cancelled> {
+        let c = self.multiply::<T>(size, seed, mode, ctl)?;
+        Ok(Output {
+            checksum: c.checksum(),
+            ops: 2 * (size as u64).pow(3),
+        })
+    }
+
+    fn run(&self, size: usize, seed: u64, mode: Mode, ctl: &Control) -> Result<Output, Cancelled> {
+        match self.precision {
+            Precision::F32 => self.run_as::<f32>(size, seed, mode, ctl),
+            Precision::F64 => self.run_as::<f64>(size, seed, mode, ctl),
+            Precision::I32 => self.run_as::<i32>(size, seed, mode, ctl),
+            Precision::I64 => self.run_as::<i64>(size, seed, mode, ctl),
+        }
+    }
+
+    // Contra a matmul-tiled, que soma na mesma ordem do i-j-k ingênuo
+    fn error_as<T: Element>(&self, size: usize, seed: u64, mode: Mode, ctl: &Control) -> Result<NumericError, Cancelled> {
+        let c = self.multiply::<T>(size, seed, mode, ctl)?;
+        let (a, b) = matrix::matmul_inputs_as::<T>(size, seed);
+        let reference = matrix::matmul_with(&a, &b, MatMulVariant::Tiled, mode, ctl)?;
+        Ok(NumericError::between(&c, &reference))
+    }
+}
+
+impl Kernel for RecursiveMatMul {
+    fn name(&self) -> &'static str {
+        match self.recursion {
+            Recursion::Blocks => "matmul-recursive",
+            Recursion::Strassen => "matmul-strassen",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self.recursion {
+            Recursion::Blocks => "Matmul NxN recursiva em quadrantes (8 produtos por nível) até o cutoff; size = N",
+            Recursion::Strassen => "Matmul NxN de Strassen (7 produtos por nível) até o cutoff; size = N",
+        }
+    }
+
+    fn default_size(&self) -> usize {
+        512
+    }
+
+    fn max_size(&self) -> usize {
+        8192
+    }
+
+    // A, B e C + os quadrantes e somas de cada nível (~N² no primeiro, 1/4 disso a cada nível abaixo),
+    // com folga para os produtos que rodam ao mesmo tempo
+    fn memory_bytes(&self, size: usize) -> u64 {
+        super::matrix_bytes(size, 10, self.precision)
+    }
+
+    fn precision(&self) -> Option<Precision> {
+        Some(self.precision)
+    }
+
+    fn cutoff(&self) -> Option<usize> {
+        Some(self.cutoff)
+    }
+
+    fn with_cutoff(&self, cutoff: usize) -> Option<Box<dyn Kernel>> {
+        (cutoff >= strassen::MIN_CUTOFF).then(|| Box::new(RecursiveMatMul { cutoff, ..*self }) as Box<dyn Kernel>)
+    }
+
+    fn numeric_error(&self, size: usize, seed: u64, mode: Mode, ctl: &Control) -> Result<Option<NumericError>, Cancelled> {
+        let error = match self.precision {
+            Precision::F32 => self.error_as::<f32>(size, seed, mode, ctl),
+            Precision::F64 => self.error_as::<f64>(size, seed, mode, ctl),
+            Precision::I32 => self.error_as::<i32>(size, seed, mode, ctl),
+            Precision::I64 => self.error_as::<i64>(size, seed, mode, ctl),
+        }?;
+        Ok(Some(error))
+    }
+
+    fn run_single(&self, size: usize, seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        self.run(size, seed, Mode::Single, ctl)
+    }
+
+    fn run_parallel(&self, size: usize, seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        self.run(size, seed, Mode::Parallel, ctl)
+    }
+}
+
+// GEMM otimizado (crate gemm: empacotamento em blocos + microkernels AVX/FMA/NEON), só na
+// feature `blas`. Não é o que o resto mede, é a régua: quão rápido a máquina multiplica de verdade
+#[cfg(feature = "blas")]
+pub struct BlasMatMul(pub Precision);
+
+#[cfg(feature = "blas")]
+impl BlasMatMul {
+    // A chamada é uma só: não dá para cancelar no meio e o progresso vai de 0 a 1 no fim
+    fn run_as<T: Element>(&self, size: usize, seed: u64, mode: Mode, ctl: &Control, one: T) -> Result<Output, Cancelled> {
+        ctl.check()?;
+        let (a, b) = matrix::matmul_inputs_as::<T>(size, seed);
+        let mut c = matrix::Matrix::<T>::zeros(size, size);
+        // Parallelism::Rayon(0) = as threads do pool rayon atual (o de ?threads, se houver)
+        let parallelism = match mode {
+            Mode::Single => gemm::Parallelism::None,
+            Mode::Parallel => gemm::Parallelism::Rayon(0),
+        };
+        let n = size as isize;
+        // Row-major: coluna anda 1, linha anda n. C = 0·C + 1·A·B (read_dst = false ignora o C)
+        // Seguro porque: A, B e C têm exatamente size*size elementos contíguos (Matrix é um Vec
+        // row-major), então com passo 1 na coluna e n na linha o maior índice lido/escrito é
+        // (size-1)·n + (size-1) < size*size; C é emprestado com &mut durante a chamada (nenhum
+        // outro acesso, e não se sobrepõe a A nem a B), e A e B só são lidos
+        unsafe {
+            gemm::gemm(
+                size,
+                size,
+                size,
+                c.as_mut_slice().as_mut_ptr(),
+                1,
+                n,
+                false,
+                a.as_slice().as_ptr(),
+                1,
+                n,
+                b.as_slice().as_ptr(),
+                1,
+                n,
+                T::default(),
+                one,
+                false,
+                false,
+                false,
+                parallelism,
+            );
+        }
+        ctl.progress(1).advance(1)?;
+        Ok(Output {
+            checksum: c.checksum(),
+            ops: 2 * (size as u64).pow(3),
+        })
+    }
+
+    fn run(&self, size: usize, seed: u64, mode: Mode, ctl: &Control) -> Result<Output, Cancelled> {
+        match self.0 {
+            Precision::F32 => self.run_as::<f32>(size, seed, mode, ctl, 1.0),
+            Precision::F64 => self.run_as::<f64>(size, seed, mode, ctl, 1.0),
+            Precision::I32 | Precision::I64 => unreachable!("o gemm só tem f32 e f64 (ver TYPED)"),
+        }
+    }
+}
+
+#[cfg(feature = "blas")]
+impl Kernel for BlasMatMul {
+    fn name(&self) -> &'static str {
+        super::BLAS_KERNEL
+    }
+
+    fn description(&self) -> &'static str {
+        "Matmul NxN pelo GEMM otimizado do crate gemm (referência do que a máquina alcança); size = N"
+    }
+
+    fn default_size(&self) -> usize {
+        400
+    }
+
+    fn max_size(&self) -> usize {
+        8192
+    }
+
+    // A, B e C; os blocos empacotados do gemm são pequenos perto disso
+    fn memory_bytes(&self, size: usize) -> u64 {
+        super::matrix_bytes(size, 3, self.0)
+    }
+
+    fn precision(&self) -> Option<Precision> {
+        Some(self.0)
+    }
+
+    fn run_single(&self, size: usize, seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        self.run(size, seed, Mode::Single, ctl)
+    }
+
+    fn run_parallel(&self, size: usize, seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        self.run(size, seed, Mode::Parallel, ctl)
+    }
+}
@@ -0,0 +1,416 @@
+// --- REGISTRO DE KERNELS ---
+// Cada kernel sabe rodar em uma thread só e em paralelo (rayon).
+// O `size` significa coisas diferentes para cada um (ver `description`).
+
+use crate::control::{Cancelled, Control};
+use crate::matrix::MatMulVariant;
+use crate::memory::MemoryProbe;
+use crate::strassen::{NumericError, Recursion};
+use crate::{BenchConfig, BenchResult, Stopwatch};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+mod fft;
+mod json;
+mod mandelbrot;
+mod matmul;
+mod nbody;
+mod sha256;
+mod sieve;
+mod sort;
+
+// O que sai de uma execução: checksum para conferir o cálculo e quantas operações foram feitas
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Output {
+    pub checksum: f64,
+    pub ops: u64,
+}
+
+// Seed das entradas pseudo-aleatórias quando ninguém escolhe outro
+pub const DEFAULT_SEED: u64 = 42;
+
+// A lei de força do n-body, para quem simula fora do kernel (sync-demo)
+pub use nbody::force as nbody_force;
+
+// Nome do kernel de referência da feature `blas` (GEMM otimizado), a régua das outras matmul
+pub const BLAS_KERNEL: &str = "matmul-blas";
+
+pub trait Kernel: Sync {
+    fn name(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    // Um tamanho que roda em poucos segundos no browser
+    fn default_size(&self) -> usize;
+    // Maior `size` aceito por quem expõe o kernel para fora (API)
+    fn max_size(&self) -> usize;
+    // Estimativa do pico de memória (bytes) de uma execução, antes de alocar qualquer coisa
+    fn memory_bytes(&self, size: usize) -> u64;
+    // Tipo do elemento desta instância (as matmul têm uma por tipo, ver TYPED);
+    // None nos kernels que não têm essa escolha
+    fn precision(&self) -> Option<Precision> {
+        None
+    }
+    // Lado em que as matmul recursivas param de dividir (None nos outros kernels)
+    fn cutoff(&self) -> Option<usize> {
+        None
+    }
+    // A mesma instância com outro cutoff; None se o kernel não tem cutoff ou se ele é
+    // pequeno demais (ver strassen::MIN_CUTOFF)
+    fn with_cutoff(&self, _cutoff: usize) -> Option<Box<dyn Kernel>> {
+        None
+    }
+    // Erro de arredondamento contra a matmul O(n³), para quem troca a ordem das contas
+    // (Strassen). Roda fora do tempo medido
+    fn numeric_error(&self, _size: usize, _seed: u64, _mode: Mode, _ctl: &Control) -> Result<Option<NumericError>, Cancelled> {
+        Ok(None)
+    }
+    // `seed` gera as entradas (kernels com entrada fixa, como o sieve, ignoram).
+    // `ctl` recebe o progresso e é consultado entre linhas/blocos para cancelar
+    fn run_single(&self, size: usize, seed: u64, ctl: &Control) -> Result<Output, Cancelled>;
+    fn run_parallel(&self, size: usize, seed: u64, ctl: &Control) -> Result<Output, Cancelled>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Single,
+    Parallel,
+}
+
+impl Default for Mode {
+    // Nativo usa todos os núcleos; wasm roda numa thread só (a não ser com a feature wasm-threads)
+    fn default() -> Self {
+        if cfg!(all(target_arch = "wasm32", not(feature = "wasm-threads"))) {
+            Mode::Single
+        } else {
+            Mode::Parallel
+        }
+    }
+}
+
+// Tipo do elemento das matmul (ver matrix::Element): f32 é o da GPU (gpu-bench)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+#[serde(rename_all = "lowercase")]
+pub enum Precision {
+    F32,
+    F64,
+    I32,
+    I64,
+}
+
+impl Precision {
+    pub const ALL: [Precision; 4] = [Precision::F64, Precision::F32, Precision::I32, Precision::I64];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Precision::F32 => "f32",
+            Precision::F64 => "f64",
+            Precision::I32 => "i32",
+            Precision::I64 => "i64",
+        }
+    }
+
+    pub fn bytes(self) -> u64 {
+        match self {
+            Precision::F32 | Precision::I32 => 4,
+            Precision::F64 | Precision::I64 => 8,
+        }
+    }
+
+    // Inteiros têm outra entrada (0..=9), então só se comparam entre si
+    pub fn is_integer(self) -> bool {
+        matches!(self, Precision::I32 | Precision::I64)
+    }
+}
+
+impl FromStr for Precision {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Precision::ALL
+            .into_iter()
+            .find(|p| p.name() == s)
+            .ok_or_else(|| format!("precisão desconhecida: {} (f64, f32, i32 ou i64)", s))
+    }
+}
+
+impl fmt::Display for Precision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+pub static KERNELS: &[&dyn Kernel] = &[
+    &matmul::MatMul(Precision::F64),
+    &matmul::FlatMatMul(MatMulVariant::Naive, Precision::F64),
+    &matmul::FlatMatMul(MatMulVariant::Ikj, Precision::F64),
+    &matmul::FlatMatMul(MatMulVariant::Tiled, Precision::F64),
+    &matmul::FlatMatMul(MatMulVariant::TransposedB, Precision::F64),
+    &matmul::FlatMatMul(MatMulVariant::Simd, Precision::F64),
+    &matmul::RecursiveMatMul::new(Recursion::Blocks, Precision::F64),
+    &matmul::RecursiveMatMul::new(Recursion::Strassen, Precision::F64),
+    #[cfg(feature = "blas")]
+    &matmul::BlasMatMul(Precision::F64),
+    &fft::Fft,
+    &sort::Sort,
+    &mandelbrot::Mandelbrot,
+    &sha256::Sha256,
+    &json::Json,
+    &nbody::NBody,
+    &sieve::Sieve,
+];
+
+// As matmul nos outros tipos de elemento (as de KERNELS são f64). Ficam fora do catálogo:
+// quem escolhe é o `precision` (find_as). A Simd só tem caminho explícito para f64 e f32,
+// e a blas (feature `blas`) só existe onde o gemm existe: f64 e f32
+static TYPED: &[&dyn Kernel] = &[
+    &matmul::MatMul(Precision::F32),
+    &matmul::MatMul(Precision::I32),
+    &matmul::MatMul(Precision::I64),
+    &matmul::FlatMatMul(MatMulVariant::Naive, Precision::F32),
+    &matmul::FlatMatMul(MatMulVariant::Naive, Precision::I32),
+    &matmul::FlatMatMul(MatMulVariant::Naive, Precision::I64),
+    &matmul::FlatMatMul(MatMulVariant::Ikj, Precision::F32),
+    &matmul::FlatMatMul(MatMulVariant::Ikj, Precision::I32),
+    &matmul::FlatMatMul(MatMulVariant::Ikj, Precision::I64),
+    &matmul::FlatMatMul(MatMulVariant::Tiled, Precision::F32),
+    &matmul::FlatMatMul(MatMulVariant::Tiled, Precision::I32),
+    &matmul::FlatMatMul(MatMulVariant::Tiled, Precision::I64),
+    &matmul::FlatMatMul(MatMulVariant::TransposedB, Precision::F32),
+    &matmul::FlatMatMul(MatMulVariant::TransposedB, Precision::I32),
+    &matmul::FlatMatMul(MatMulVariant::TransposedB, Precision::I64),
+    &matmul::FlatMatMul(MatMulVariant::Simd, Precision::F32),
+    &matmul::RecursiveMatMul::new(Recursion::Blocks, Precision::F32),
+    &matmul::RecursiveMatMul::new(Recursion::Blocks, Precision::I32),
+    &matmul::RecursiveMatMul::new(Recursion::Blocks, Precision::I64),
+    &matmul::RecursiveMatMul::new(Recursion::Strassen, Precision::F32),
+    &matmul::RecursiveMatMul::new(Recursion::Strassen, Precision::I32),
+    &matmul::RecursiveMatMul::new(Recursion::Strassen, Precision::I64),
+    #[cfg(feature = "blas")]
+    &matmul::BlasMatMul(Precision::F32),
+];
+
+// Nome + descrição de cada kernel, para montar menus (API e frontend)
+#[derive(Serialize, Debug, Clone)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct KernelInfo {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub default_size: usize,
+    pub max_size: usize,
+    // Tipos de elemento aceitos, o padrão primeiro (vazio: o kernel não tem essa escolha)
+    pub precisions: Vec<Precision>,
+    // Cutoff padrão das matmul recursivas (None: o kernel não tem cutoff)
+    pub cutoff: Option<usize>,
+}
+
+pub fn catalog() -> Vec<KernelInfo> {
+    KERNELS
+        .iter()
+        .map(|k| KernelInfo {
+            name: k.name(),
+            description: k.description(),
+            default_size: k.default_size(),
+            max_size: k.max_size(),
+            precisions: precisions(k.name()),
+            cutoff: k.cutoff(),
+        })
+        .collect()
+}
+
+pub fn find(name: &str) -> Option<&'static dyn Kernel> {
+    KERNELS.iter().copied().find(|k| k.name() == name)
+}
+
+// O kernel no tipo de elemento pedido (None: o do registro). None se o nome não existe ou
+// se o kernel não tem esse tipo
+pub fn find_as(name: &str, precision: Option<Precision>) -> Option<&'static dyn Kernel> {
+    match precision {
+        None => find(name),
+        Some(p) => KERNELS
+            .iter()
+            .chain(TYPED)
+            .copied()
+            .find(|k| k.name() == name && k.precision() == Some(p)),
+    }
+}
+
+pub fn precisions(name: &str) -> Vec<Precision> {
+    KERNELS
+        .iter()
+        .chain(TYPED)
+        .filter(|k| k.name() == name)
+        .filter_map(|k| k.precision())
+        .collect()
+}
+
+// Para quem expõe o kernel (API, frontend): confere o nome, a precisão e o cutoff do
+// `config` e o erro já vem com a mensagem para o usuário. Devolve a instância do registro
+// (sem o cutoff, que o harness aplica; serve para as estimativas de memória)
+pub fn resolve(name: &str, config: &BenchConfig) -> Result<&'static dyn Kernel, String> {
+    let kernel = find(name).ok_or_else(|| format!("kernel desconhecido: {}", name))?;
+    let kernel = match (config.precision, precisions(name)) {
+        (None, _) => kernel,
+        (Some(_), accepted) if accepted.is_empty() => return Err(format!("{} não tem escolha de precisão", name)),
+        (Some(p), accepted) => find_as(name, Some(p)).ok_or_else(|| {
+            let accepted: Vec<&str> = accepted.iter().map(|p| p.name()).collect();
+            format!("{} não tem versão {} (aceita {})", name, p, accepted.join(", "))
+        })?,
+    };
+    match config.cutoff {
+        Some(_) if kernel.cutoff().is_none() => Err(format!("{} não tem cutoff", name)),
+        Some(cutoff) if kernel.with_cutoff(cutoff).is_none() => {
+            Err(format!("cutoff deve ser pelo menos {}", crate::strassen::MIN_CUTOFF))
+        }
+        _ => Ok(kernel),
+    }
+}
+
+// Roda um kernel pelo nome (com DEFAULT_SEED) e mede o tempo. None se o nome não existe
+pub fn run_kernel(name: &str, size: usize, mode: Mode) -> Option<BenchResult> {
+    let kernel = find(name)?;
+
+    let probe = MemoryProbe::start();
+    let start = Stopwatch::start();
+    let output = match mode {
+        Mode::Single => kernel.run_single(size, DEFAULT_SEED, &Control::NONE),
+        Mode::Parallel => kernel.run_parallel(size, DEFAULT_SEED, &Control::NONE),
+    }
+    .expect("sem token de cancelamento");
+    let elapsed_ms = start.elapsed_ms();
+
+    let mut result = BenchResult::new(
+        kernel.name(),
+        size,
+        DEFAULT_SEED,
+        elapsed_ms,
+        threads_for(mode),
+        output.checksum,
+        output.ops,
+    );
+    result.precision = kernel.precision();
+    result.cutoff = kernel.cutoff();
+    result.numeric_error = kernel.numeric_error(size, DEFAULT_SEED, mode, &Control::NONE).expect("sem token de cancelamento");
+    result.memory = probe.finish(1);
+    Some(result)
+}
+
+// `count` matrizes NxN (saturando em vez de estourar para N absurdos)
+fn matrix_bytes(size: usize, count: u64, precision: Precision) -> u64 {
+    (size as u64).saturating_mul(size as u64).saturating_mul(precision.bytes() * count)
+}
+
+pub(crate) fn threads_for(mode: Mode) -> usize {
+    match mode {
+        Mode::Single => 1,
+        Mode::Parallel => rayon::current_num_threads(),
+    }
+}
+
+// Gerador pseudo-aleatório simples (xorshift64*), para entradas determinísticas
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Rng(seed.max(1))
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    // Uniforme em [0, 1)
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Versão paralela e versão de uma thread têm que fazer exatamente a mesma conta
+    #[test]
+    fn single_and_parallel_agree() {
+        for kernel in KERNELS.iter().chain(TYPED) {
+            let size = (kernel.default_size() / 8).max(2);
+            let single = kernel.run_single(size, DEFAULT_SEED, &Control::NONE).unwrap();
+            let parallel = kernel.run_parallel(size, DEFAULT_SEED, &Control::NONE).unwrap();
+            assert_eq!(single, parallel, "kernel {}", kernel.name());
+            assert!(single.ops > 0, "kernel {}", kernel.name());
+        }
+    }
+
+    // Todo kernel chega a 100% e para com Err(Cancelled) se o token já foi cancelado
+    #[test]
+    fn kernels_report_progress_and_cancel() {
+        use crate::CancelToken;
+        use std::sync::Mutex;
+
+        for kernel in KERNELS {
+            let size = (kernel.default_size() / 8).max(2);
+            let last = Mutex::new(0.0);
+            let report = |f: f64| *last.lock().unwrap() = f;
+            let token = CancelToken::new();
+            let ctl = Control::new(Some(&token), Some(&report));
+            assert!(kernel.run_parallel(size, DEFAULT_SEED, &ctl).is_ok());
+            assert_eq!(*last.lock().unwrap(), 1.0, "kernel {}", kernel.name());
+
+            token.cancel();
+            assert_eq!(kernel.run_single(size, DEFAULT_SEED, &ctl), Err(Cancelled), "kernel {}", kernel.name());
+        }
+    }
+
+    #[test]
+    fn limits_cover_the_default_size() {
+        for kernel in KERNELS {
+            assert!(kernel.default_size() <= kernel.max_size(), "kernel {}", kernel.name());
+        }
+        // O caso que derrubaria o servidor: três (quatro, no pico) matrizes 100000x100000
+        assert_eq!(find("matmul").unwrap().memory_bytes(100_000), 4 * 8 * 100_000 * 100_000);
+        assert_eq!(find("matmul-tiled").unwrap().memory_bytes(usize::MAX), u64::MAX);
+        assert_eq!(find_as("matmul", Some(Precision::F32)).unwrap().memory_bytes(100), 4 * 4 * 100 * 100);
+    }
+
+    #[test]
+    fn find_by_precision() {
+        assert_eq!(precisions("matmul"), Precision::ALL);
+        assert_eq!(precisions("matmul-simd"), [Precision::F64, Precision::F32]);
+        assert!(precisions("sieve").is_empty());
+
+        let config = |precision, cutoff| BenchConfig {
+            precision,
+            cutoff,
+            ..BenchConfig::default()
+        };
+        let f32 = resolve("matmul-tiled", &config(Some(Precision::F32), None)).unwrap();
+        assert_eq!((f32.name(), f32.precision()), ("matmul-tiled", Some(Precision::F32)));
+        assert_eq!(resolve("matmul", &config(None, None)).unwrap().precision(), Some(Precision::F64));
+        let error = resolve("matmul-simd", &config(Some(Precision::I32), None)).err().unwrap();
+        assert!(error.contains("aceita f64, f32"), "{}", error);
+        assert!(resolve("sieve", &config(Some(Precision::F32), None)).is_err());
+        assert!(resolve("nao-existe", &config(None, None)).is_err());
+
+        let strassen = resolve("matmul-strassen", &config(Some(Precision::I32), Some(32))).unwrap();
+        assert_eq!((strassen.precision(), strassen.cutoff()), (Some(Precision::I32), Some(64)));
+        assert!(resolve("matmul-strassen", &config(None, Some(8))).is_err());
+        assert!(resolve("matmul-tiled", &config(None, Some(32))).is_err());
+        assert_eq!("i64".parse(), Ok(Precision::I64));
+        assert!("f16".parse::<Precision>().is_err());
+
+        #[cfg(feature = "blas")]
+        assert_eq!(precisions(BLAS_KERNEL), [Precision::F64, Precision::F32]);
+    }
+
+    #[test]
+    fn run_kernel_by_name() {
+        let result = run_kernel("sieve", 1000, Mode::Single).unwrap();
+        // 168 primos abaixo de 1000
+        assert_eq!(result.checksum, 168.0);
+        assert_eq!(result.threads, 1);
+        assert!(run_kernel("nao-existe", 10, Mode::Single).is_none());
+    }
+}
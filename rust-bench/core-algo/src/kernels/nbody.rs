@@ -0,0 +1,115 @@
+use super::{Kernel, Output, Rng};
+use crate::control::{Cancelled, Control};
+use rayon::prelude::*;
+
+// Um passo do n-body O(N²), mesma lei de força do `cpu-nbody` e do shader do `gpu-physics`
+pub struct NBody;
+
+#[derive(Clone, Copy)]
+struct Particle {
+    pos: [f32; 2],
+    vel: [f32; 2],
+}
+
+fn particles(size: usize, seed: u64) -> Vec<Particle> {
+    // No `cpu-nbody` todas começam em (0.5, 0.5); aqui espalhamos para a força não ser zero
+    let mut rng = Rng::new(seed);
+    (0..size)
+        .map(|_| Particle {
+            pos: [rng.next_f64() as f32, rng.next_f64() as f32],
+            vel: [0.0, 0.0],
+        })
+        .collect()
+}
+
+// Suavização (partículas quase coladas não explodem) e constante da força
+pub const SOFTENING: f32 = 0.01;
+pub const GRAVITY: f32 = 0.0001;
+
+// Força total sobre a partícula em `pos`, atraída por cada uma de `others` (ela mesma pode
+// estar na lista: dx = dy = 0 não soma nada). Também é a física do servidor do sync-demo
+pub fn force(pos: [f32; 2], others: impl IntoIterator<Item = [f32; 2]>) -> [f32; 2] {
+    let mut force_x = 0.0;
+    let mut force_y = 0.0;
+
+    for other in others {
+        let dx = other[0] - pos[0];
+        let dy = other[1] - pos[1];
+
+        let dist_sq = dx * dx + dy * dy + SOFTENING;
+        let dist = dist_sq.sqrt();
+
+        let f = GRAVITY / (dist_sq * dist);
+
+        force_x += dx * f;
+        force_y += dy * f;
+    }
+    [force_x, force_y]
+}
+
+fn step(p: &Particle, old: &[Particle]) -> Particle {
+    let [force_x, force_y] = force(p.pos, old.iter().map(|o| o.pos));
+    let vel = [p.vel[0] + force_x, p.vel[1] + force_y];
+    Particle {
+        pos: [p.pos[0] + vel[0], p.pos[1] + vel[1]],
+        vel,
+    }
+}
+
+fn output(particles: &[Particle]) -> Output {
+    let n = particles.len() as u64;
+    Output {
+        checksum: particles.iter().map(|p| (p.pos[0] + p.pos[1]) as f64).sum(),
+        // Interações (como o "G-Interações/s" do cpu-nbody)
+        ops: n * n,
+    }
+}
+
+impl Kernel for NBody {
+    fn name(&self) -> &'static str {
+        "nbody"
+    }
+
+    fn description(&self) -> &'static str {
+        "Um passo de n-body O(N²) em f32; size = partículas (ops = interações)"
+    }
+
+    fn default_size(&self) -> usize {
+        4096
+    }
+
+    fn max_size(&self) -> usize {
+        200_000
+    }
+
+    // Partículas antes e depois do passo (16 bytes cada)
+    fn memory_bytes(&self, size: usize) -> u64 {
+        (size as u64).saturating_mul(32)
+    }
+
+    fn run_single(&self, size: usize, seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        let old = particles(size, seed);
+        let progress = ctl.progress(size);
+        let new: Vec<Particle> = old
+            .iter()
+            .map(|p| {
+                let particle = step(p, &old);
+                progress.advance(1).map(|_| particle)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(output(&new))
+    }
+
+    fn run_parallel(&self, size: usize, seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        let old = particles(size, seed);
+        let progress = ctl.progress(size);
+        let new: Vec<Particle> = old
+            .par_iter()
+            .map(|p| {
+                let particle = step(p, &old);
+                progress.advance(1).map(|_| particle)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(output(&new))
+    }
+}
@@ -0,0 +1,70 @@
+use super::{Kernel, Output};
+use crate::control::{Cancelled, Control};
+use rayon::prelude::*;
+use sha2::{Digest, Sha256 as Hasher};
+
+// SHA-256 de N mensagens independentes de 1 KiB
+pub struct Sha256;
+
+const MESSAGE_BYTES: usize = 1024;
+
+// Gera a mensagem i e devolve os 4 primeiros bytes do hash
+fn hash_message(i: usize) -> u64 {
+    let message: Vec<u8> = (0..MESSAGE_BYTES).map(|j| (i.wrapping_mul(31) + j) as u8).collect();
+    let digest = Hasher::digest(&message);
+    u32::from_le_bytes([digest[0], digest[1], digest[2], digest[3]]) as u64
+}
+
+fn output(sum: u64, size: usize) -> Output {
+    Output {
+        checksum: sum as f64,
+        // Bytes processados
+        ops: (size * MESSAGE_BYTES) as u64,
+    }
+}
+
+impl Kernel for Sha256 {
+    fn name(&self) -> &'static str {
+        "sha256"
+    }
+
+    fn description(&self) -> &'static str {
+        "SHA-256 de mensagens de 1 KiB; size = quantidade de mensagens (ops = bytes)"
+    }
+
+    fn default_size(&self) -> usize {
+        50_000
+    }
+
+    fn max_size(&self) -> usize {
+        10_000_000
+    }
+
+    // Uma mensagem de cada vez por thread
+    fn memory_bytes(&self, _size: usize) -> u64 {
+        (MESSAGE_BYTES * rayon::current_num_threads()) as u64
+    }
+
+    fn run_single(&self, size: usize, _seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        let progress = ctl.progress(size);
+        let sum = (0..size)
+            .map(|i| {
+                let hash = hash_message(i);
+                progress.advance(1).map(|_| hash)
+            })
+            .sum::<Result<u64, _>>()?;
+        Ok(output(sum, size))
+    }
+
+    fn run_parallel(&self, size: usize, _seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        let progress = ctl.progress(size);
+        let sum = (0..size)
+            .into_par_iter()
+            .map(|i| {
+                let hash = hash_message(i);
+                progress.advance(1).map(|_| hash)
+            })
+            .sum::<Result<u64, _>>()?;
+        Ok(output(sum, size))
+    }
+}
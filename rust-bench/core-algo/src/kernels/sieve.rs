@@ -0,0 +1,109 @@
+use super::{Kernel, Output};
+use crate::control::{Cancelled, Control};
+use rayon::prelude::*;
+
+// Crivo de Eratóstenes segmentado: conta os primos menores que N
+pub struct Sieve;
+
+// Segmentos de 32 KiB cabem na L1
+const SEGMENT: usize = 32 * 1024;
+
+// Primos até sqrt(limit), com o crivo simples
+fn base_primes(limit: usize) -> Vec<usize> {
+    let root = (limit as f64).sqrt() as usize + 1;
+    let mut is_composite = vec![false; root + 1];
+    let mut primes = Vec::new();
+    for i in 2..=root {
+        if !is_composite[i] {
+            primes.push(i);
+            let mut j = i * i;
+            while j <= root {
+                is_composite[j] = true;
+                j += i;
+            }
+        }
+    }
+    primes
+}
+
+// Quantos primos existem em [low, high)
+fn count_segment(low: usize, high: usize, primes: &[usize]) -> u64 {
+    let mut is_composite = vec![false; high - low];
+    for &p in primes {
+        if p * p >= high {
+            break;
+        }
+        let mut j = (low.div_ceil(p) * p).max(p * p);
+        while j < high {
+            is_composite[j - low] = true;
+            j += p;
+        }
+    }
+    (low..high)
+        .zip(&is_composite)
+        .filter(|(n, composite)| *n >= 2 && !**composite)
+        .count() as u64
+}
+
+fn segments(size: usize) -> impl Iterator<Item = (usize, usize)> {
+    (0..size).step_by(SEGMENT).map(move |low| (low, (low + SEGMENT).min(size)))
+}
+
+fn output(count: u64, size: usize) -> Output {
+    Output {
+        checksum: count as f64,
+        // Números examinados
+        ops: size as u64,
+    }
+}
+
+impl Kernel for Sieve {
+    fn name(&self) -> &'static str {
+        "sieve"
+    }
+
+    fn description(&self) -> &'static str {
+        "Crivo de Eratóstenes segmentado; size = limite superior (conta primos < N)"
+    }
+
+    fn default_size(&self) -> usize {
+        50_000_000
+    }
+
+    // Ainda cabe em usize no wasm32
+    fn max_size(&self) -> usize {
+        4_000_000_000
+    }
+
+    // Primos base + um segmento por thread + a lista de segmentos do modo paralelo
+    fn memory_bytes(&self, size: usize) -> u64 {
+        let root = (size as f64).sqrt() as u64 + 1;
+        root * 9 + (SEGMENT * rayon::current_num_threads()) as u64 + size.div_ceil(SEGMENT) as u64 * 16
+    }
+
+    fn run_single(&self, size: usize, _seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        let primes = base_primes(size);
+        let progress = ctl.progress(size.div_ceil(SEGMENT));
+        let count = segments(size)
+            .map(|(l, h)| {
+                let count = count_segment(l, h, &primes);
+                progress.advance(1).map(|_| count)
+            })
+            .sum::<Result<u64, _>>()?;
+        Ok(output(count, size))
+    }
+
+    fn run_parallel(&self, size: usize, _seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        let primes = base_primes(size);
+        let segments: Vec<_> = segments(size).collect();
+        let progress = ctl.progress(segments.len());
+        let count = segments
+            .into_par_iter()
+            .map(|(l, h)| {
+                let count = count_segment(l, h, &primes);
+                progress.advance(1).map(|_| count)
+            })
+            .sum::<Result<u64, _>>()?;
+        Ok(output(count, size))
+    }
+}
@@ -0,0 +1,66 @@
+use super::{Kernel, Output, Rng};
+use crate::control::{Cancelled, Control};
+use rayon::prelude::*;
+
+// Ordenação de inteiros pseudo-aleatórios (sort_unstable vs par_sort_unstable)
+pub struct Sort;
+
+fn input(size: usize, seed: u64) -> Vec<u64> {
+    let mut rng = Rng::new(seed);
+    (0..size).map(|_| rng.next_u64()).collect()
+}
+
+fn output(sorted: &[u64]) -> Output {
+    debug_assert!(sorted.windows(2).all(|w| w[0] <= w[1]));
+    let n = sorted.len() as u64;
+    // Soma ponderada pela posição: muda se a ordem estiver errada
+    let checksum = sorted
+        .iter()
+        .enumerate()
+        .fold(0u64, |acc, (i, x)| acc.wrapping_add(x.wrapping_mul(i as u64 + 1)));
+    Output {
+        checksum: checksum as f64,
+        // Comparações esperadas: N log2 N
+        ops: n * (64 - n.leading_zeros()) as u64,
+    }
+}
+
+impl Kernel for Sort {
+    fn name(&self) -> &'static str {
+        "sort"
+    }
+
+    fn description(&self) -> &'static str {
+        "Ordenação de u64 pseudo-aleatórios; size = quantidade de elementos"
+    }
+
+    fn default_size(&self) -> usize {
+        2_000_000
+    }
+
+    fn max_size(&self) -> usize {
+        1 << 30
+    }
+
+    // Só o vetor: os dois sorts são in-place
+    fn memory_bytes(&self, size: usize) -> u64 {
+        (size as u64).saturating_mul(8)
+    }
+
+    // O sort da std não tem onde parar no meio: progresso só antes e depois
+    fn run_single(&self, size: usize, seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        let mut data = input(size, seed);
+        ctl.check()?;
+        data.sort_unstable();
+        ctl.progress(1).advance(1)?;
+        Ok(output(&data))
+    }
+
+    fn run_parallel(&self, size: usize, seed: u64, ctl: &Control) -> Result<Output, Cancelled> {
+        let mut data = input(size, seed);
+        ctl.check()?;
+        data.par_sort_unstable();
+        ctl.progress(1).advance(1)?;
+        Ok(output(&data))
+    }
+}
@@ -1,52 +1,27 @@
-#[cfg(not(target_arch = "wasm32"))]
-use rayon::prelude::*;
+mod clock;
+pub mod control;
+pub mod harness;
+pub mod kernels;
+pub mod matrix;
+pub mod memory;
+mod result;
+pub mod scaling;
+mod simd;
+pub mod strassen;
+pub mod verify;
+pub use clock::Stopwatch;
+pub use control::{CancelToken, Cancelled, Control, Progress};
+pub use harness::{bench_kernel, bench_kernel_with, BenchConfig, Stats};
+pub use kernels::{catalog, run_kernel, Kernel, KernelInfo, Mode, Precision, DEFAULT_SEED, KERNELS};
+pub use memory::{CountingAlloc, MemoryStats};
+pub use matrix::{matmul, matmul_inputs, matmul_inputs_as, matmul_with, weighted_checksum, Element, MatMulVariant, Matrix};
+pub use result::{BenchResult, TARGET};
+pub use scaling::{bench_with_threads, check_threads, sweep, thread_pool, Scaling, ScalingPoint};
+pub use simd::backend as simd_backend;
+pub use strassen::{recursive_matmul, NumericError, Recursion};
+pub use verify::{verify, Verification};
 
-pub fn heavy_computation(size: usize) -> String {
-    #[cfg(not(target_arch = "wasm32"))]
-    let start = std::time::Instant::now();
-
-    let mut matrix_a = vec![vec![1.5f64; size]; size];
-    let matrix_b = vec![vec![2.5f64; size]; size];
-    // Inicializa com zeros
-    let mut result = vec![vec![0.0f64; size]; size];
-
-    // --- A MÁGICA DO PARALELISMO AQUI ---
-    
-    // VERSÃO NATIVA (Multithread - Rayon)
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        // par_iter_mut() divide as linhas da matriz entre os núcleos da CPU automaticamente
-        result.par_iter_mut().enumerate().for_each(|(i, row)| {
-            for j in 0..size {
-                for k in 0..size {
-                    row[j] += matrix_a[i][k] * matrix_b[k][j];
-                }
-            }
-        });
-    }
-
-    // VERSÃO WASM (Single Thread - Loop Normal)
-    #[cfg(target_arch = "wasm32")]
-    {
-        for i in 0..size {
-            for j in 0..size {
-                for k in 0..size {
-                    result[i][j] += matrix_a[i][k] * matrix_b[k][j];
-                }
-            }
-        }
-    }
-    // ------------------------------------
-
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        let duration = start.elapsed();
-        // Adicionei uma flag "(MT)" para saber que foi MultiThread
-        return format!("Processado matriz {}x{} em {:.2?} (MT - {} cores)", size, size, duration, rayon::current_num_threads());
-    }
-
-    #[cfg(target_arch = "wasm32")]
-    {
-        return format!("Processado matriz {}x{} (WASM Single-Core)", size, size);
-    }
-}
\ No newline at end of file
+// O benchmark original: matmul, multithread no nativo e monocore no wasm
+pub fn heavy_computation(size: usize) -> BenchResult {
+    run_kernel("matmul", size, Mode::default()).expect("matmul está no registro")
+}
@@ -0,0 +1,413 @@
+// --- MATRIZ CONTÍGUA (row-major) ---
+// O `Vec<Vec<f64>>` da matmul original espalha as linhas pela memória e o laço i-j-k
+// anda pelas colunas de B, então mede mais cache miss do que o runtime.
+// Aqui os dados ficam num Vec só e dá para escolher a ordem dos laços.
+// O tipo do elemento é genérico (f64 por padrão; f32 para comparar com a GPU, i32/i64).
+
+use crate::control::{Cancelled, Control};
+use crate::kernels::{Precision, Rng};
+use crate::simd::{self, Axpy};
+use crate::Mode;
+use rayon::prelude::*;
+use std::fmt::Debug;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, Sub};
+
+// Lado do bloco da versão tiled: 64x64 f64 = 32 KiB, cabe na L1/L2
+pub const TILE: usize = 64;
+
+// O que dá para guardar numa Matrix e multiplicar
+pub trait Element:
+    Copy + Default + PartialEq + Debug + Send + Sync + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + AddAssign + Sum + 'static
+{
+    const PRECISION: Precision;
+
+    // Um valor da entrada a partir de um uniforme em [0, 1): os floats usam o próprio valor
+    // (o f32 arredonda o mesmo número do f64, como na GPU); os inteiros viram 0..=9, e a soma
+    // de N produtos (até 81 * N) cabe no i32 para qualquer size aceito
+    fn from_unit(x: f64) -> Self;
+    fn to_f64(self) -> f64;
+
+    // O laço interno da variante Simd
+    fn axpy() -> Axpy<Self> {
+        simd::axpy_scalar::<Self>
+    }
+}
+
+impl Element for f64 {
+    const PRECISION: Precision = Precision::F64;
+
+    fn from_unit(x: f64) -> Self {
+        x
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn axpy() -> Axpy<Self> {
+        simd::axpy()
+    }
+}
+
+impl Element for f32 {
+    const PRECISION: Precision = Precision::F32;
+
+    fn from_unit(x: f64) -> Self {
+        x as f32
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn axpy() -> Axpy<Self> {
+        simd::axpy_f32()
+    }
+}
+
+impl Element for i32 {
+    const PRECISION: Precision = Precision::I32;
+
+    fn from_unit(x: f64) -> Self {
+        (x * 10.0) as i32
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Element for i64 {
+    const PRECISION: Precision = Precision::I64;
+
+    fn from_unit(x: f64) -> Self {
+        (x * 10.0) as i64
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix<T = f64> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+impl<T: Element> Matrix<T> {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self::filled(rows, cols, T::default())
+    }
+
+    pub fn filled(rows: usize, cols: usize, value: T) -> Self {
+        Matrix {
+            rows,
+            cols,
+            data: vec![value; rows * cols],
+        }
+    }
+
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let data = (0..rows * cols).map(|idx| f(idx / cols, idx % cols)).collect();
+        Matrix { rows, cols, data }
+    }
+
+    // Uniforme em [0, 1) (0..=9 nos inteiros, ver Element::from_unit): positivos, então o
+    // checksum não some por cancelamento
+    pub fn random(rows: usize, cols: usize, seed: u64) -> Self {
+        let mut rng = Rng::new(seed);
+        Matrix::from_fn(rows, cols, |_, _| T::from_unit(rng.next_f64()))
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> T {
+        self.data[i * self.cols + j]
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    pub fn transpose(&self) -> Self {
+        Matrix::from_fn(self.cols, self.rows, |i, j| self.get(j, i))
+    }
+
+    pub fn checksum(&self) -> f64 {
+        weighted_checksum(&self.data)
+    }
+
+    pub fn max_abs_diff(&self, other: &Self) -> f64 {
+        assert_eq!((self.rows, self.cols), (other.rows, other.cols));
+        self.data
+            .iter()
+            .zip(&other.data)
+            .map(|(x, y)| (x.to_f64() - y.to_f64()).abs())
+            .fold(0.0, f64::max)
+    }
+}
+
+// A e B de todas as matmul (CPU, wasm e GPU) para um seed: quem usa o mesmo seed
+// e o mesmo tamanho tem que chegar no mesmo checksum
+pub fn matmul_inputs(size: usize, seed: u64) -> (Matrix, Matrix) {
+    matmul_inputs_as(size, seed)
+}
+
+// As mesmas entradas em outro tipo de elemento (f32 = as do f64 arredondadas)
+pub fn matmul_inputs_as<T: Element>(size: usize, seed: u64) -> (Matrix<T>, Matrix<T>) {
+    (
+        Matrix::random(size, size, seed),
+        Matrix::random(size, size, seed.wrapping_add(1)),
+    )
+}
+
+// Soma dos elementos (row-major) com peso 1..=13 pela posição: só somar não
+// percebe elementos trocados de lugar. A soma é sempre em f64, qualquer que seja o elemento
+pub fn weighted_checksum<T: Element>(values: &[T]) -> f64 {
+    values
+        .iter()
+        .enumerate()
+        .map(|(idx, x)| x.to_f64() * (idx % 13 + 1) as f64)
+        .sum()
+}
+
+// Ordem dos laços / layout usado na multiplicação
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatMulVariant {
+    // i-j-k: o mesmo laço da versão original, anda pelas colunas de B
+    Naive,
+    // i-k-j: a linha de B é lida em sequência (amigável para cache e vetorização)
+    Ikj,
+    // i-k-j em blocos de TILE x TILE, para reaproveitar o que está no cache
+    Tiled,
+    // Transpõe B antes: cada elemento vira produto escalar de duas linhas
+    TransposedB,
+    // Tiled com o laço interno em SIMD explícito (ver simd.rs)
+    Simd,
+}
+
+impl MatMulVariant {
+    // Quantas linhas do resultado cada tarefa paralela calcula
+    fn band_rows(self) -> usize {
+        match self {
+            MatMulVariant::Tiled | MatMulVariant::Simd => TILE,
+            _ => 1,
+        }
+    }
+}
+
+// C = A * B. Em Mode::Parallel as faixas de linhas de C são divididas entre os núcleos
+pub fn matmul<T: Element>(a: &Matrix<T>, b: &Matrix<T>, variant: MatMulVariant, mode: Mode) -> Matrix<T> {
+    matmul_with(a, b, variant, mode, &Control::NONE).expect("sem token de cancelamento")
+}
+
+// Igual a `matmul`, avisando o progresso e checando o cancelamento a cada faixa de linhas
+pub fn matmul_with<T: Element>(
+    a: &Matrix<T>,
+    b: &Matrix<T>,
+    variant: MatMulVariant,
+    mode: Mode,
+    ctl: &Control,
+) -> Result<Matrix<T>, Cancelled> {
+    assert_eq!(a.cols, b.rows, "dimensões incompatíveis");
+    let mut c = Matrix::zeros(a.rows, b.cols);
+    if c.data.is_empty() {
+        return Ok(c);
+    }
+
+    // A transposta entra no tempo medido: faz parte do custo da escolha
+    let bt = (variant == MatMulVariant::TransposedB).then(|| b.transpose());
+    let axpy = T::axpy();
+    let band_len = variant.band_rows() * b.cols;
+    let progress = ctl.progress(a.rows);
+
+    let compute = |(band, out): (usize, &mut [T])| {
+        ctl.check()?;
+        let first_row = band * variant.band_rows();
+        match variant {
+            MatMulVariant::Naive => naive_band(a, b, out, first_row),
+            MatMulVariant::Ikj => ikj_band(a, b, out, first_row),
+            MatMulVariant::Tiled => tiled_band(a, b, out, first_row, simd::axpy_scalar),
+            MatMulVariant::TransposedB => transposed_band(a, bt.as_ref().unwrap(), out, first_row),
+            MatMulVariant::Simd => tiled_band(a, b, out, first_row, axpy),
+        }
+        progress.advance(out.len() / b.cols)
+    };
+
+    match mode {
+        Mode::Single => c.data.chunks_mut(band_len).enumerate().try_for_each(compute)?,
+        Mode::Parallel => c.data.par_chunks_mut(band_len).enumerate().try_for_each(compute)?,
+    }
+
+    Ok(c)
+}
+
+fn naive_band<T: Element>(a: &Matrix<T>, b: &Matrix<T>, out: &mut [T], first_row: usize) {
+    let n = b.cols;
+    for (r, row) in out.chunks_mut(n).enumerate() {
+        let i = first_row + r;
+        for (j, value) in row.iter_mut().enumerate() {
+            for k in 0..a.cols {
+                *value += a.get(i, k) * b.get(k, j);
+            }
+        }
+    }
+}
+
+fn ikj_band<T: Element>(a: &Matrix<T>, b: &Matrix<T>, out: &mut [T], first_row: usize) {
+    let n = b.cols;
+    for (r, row) in out.chunks_mut(n).enumerate() {
+        let a_row = a.row(first_row + r);
+        for (k, &a_ik) in a_row.iter().enumerate() {
+            for (value, &b_kj) in row.iter_mut().zip(b.row(k)) {
+                *value += a_ik * b_kj;
+            }
+        }
+    }
+}
+
+fn tiled_band<T: Element>(a: &Matrix<T>, b: &Matrix<T>, out: &mut [T], first_row: usize, axpy: Axpy<T>) {
+    let n = b.cols;
+    for kk in (0..a.cols).step_by(TILE) {
+        let k_end = (kk + TILE).min(a.cols);
+        for jj in (0..n).step_by(TILE) {
+            let j_end = (jj + TILE).min(n);
+            for (r, row) in out.chunks_mut(n).enumerate() {
+                let a_row = a.row(first_row + r);
+                let row = &mut row[jj..j_end];
+                for (k, &a_ik) in a_row.iter().enumerate().take(k_end).skip(kk) {
+                    axpy(row, a_ik, &b.row(k)[jj..j_end]);
+                }
+            }
+        }
+    }
+}
+
+fn transposed_band<T: Element>(a: &Matrix<T>, bt: &Matrix<T>, out: &mut [T], first_row: usize) {
+    let n = bt.rows;
+    for (r, row) in out.chunks_mut(n).enumerate() {
+        let a_row = a.row(first_row + r);
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = a_row.iter().zip(bt.row(j)).map(|(&x, &y)| x * y).sum();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random(rows: usize, cols: usize, seed: u64) -> Matrix {
+        Matrix::random(rows, cols, seed)
+    }
+
+    const VARIANTS: [MatMulVariant; 5] = [
+        MatMulVariant::Naive,
+        MatMulVariant::Ikj,
+        MatMulVariant::Tiled,
+        MatMulVariant::TransposedB,
+        MatMulVariant::Simd,
+    ];
+
+    // Multiplicação de referência direto da definição, sem nada de otimização
+    fn reference<T: Element>(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
+        Matrix::from_fn(a.rows(), b.cols(), |i, j| (0..a.cols()).map(|k| a.get(i, k) * b.get(k, j)).sum())
+    }
+
+    #[test]
+    fn variants_match_naive() {
+        // Tamanhos que não são múltiplos do TILE, e matrizes não quadradas
+        for (m, k, n) in [(1, 1, 1), (3, 5, 2), (70, 45, 130), (TILE + 1, TILE * 2 + 3, TILE - 1)] {
+            let a = random(m, k, 1);
+            let b = random(k, n, 2);
+            let expected = reference(&a, &b);
+
+            for variant in VARIANTS {
+                for mode in [Mode::Single, Mode::Parallel] {
+                    let c = matmul(&a, &b, variant, mode);
+                    assert!(
+                        c.max_abs_diff(&expected) < 1e-12 * k as f64,
+                        "{:?} {:?} {}x{}x{}",
+                        variant,
+                        mode,
+                        m,
+                        k,
+                        n
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_precision_matches_reference() {
+        fn check<T: Element>(tolerance: f64) {
+            let (a, b) = matmul_inputs_as::<T>(TILE + 6, 5);
+            let expected = reference(&a, &b);
+            for variant in VARIANTS {
+                let c = matmul(&a, &b, variant, Mode::Parallel);
+                assert!(c.max_abs_diff(&expected) <= tolerance, "{:?} {:?}", T::PRECISION, variant);
+            }
+        }
+        check::<f32>(1e-3);
+        // Inteiros: conta exata
+        check::<i32>(0.0);
+        check::<i64>(0.0);
+
+        // f32 = as entradas do f64 arredondadas; i32 e i64 = os mesmos inteiros
+        let (a64, _) = matmul_inputs(8, 5);
+        let (a32, _) = matmul_inputs_as::<f32>(8, 5);
+        assert!(a32.as_slice().iter().zip(a64.as_slice()).all(|(&x, &y)| x == y as f32));
+        let (i32s, _) = matmul_inputs_as::<i32>(8, 5);
+        let (i64s, _) = matmul_inputs_as::<i64>(8, 5);
+        assert!(i32s.as_slice().iter().zip(i64s.as_slice()).all(|(&x, &y)| x as i64 == y && (0..=9).contains(&x)));
+    }
+
+    #[test]
+    fn simd_matches_scalar_tiled() {
+        let (a, b) = (random(150, 97, 3), random(97, 131, 4));
+        let scalar = matmul(&a, &b, MatMulVariant::Tiled, Mode::Single);
+        let simd = matmul(&a, &b, MatMulVariant::Simd, Mode::Parallel);
+        assert!(simd.max_abs_diff(&scalar) < 1e-12, "backend {}", crate::simd_backend());
+    }
+
+    #[test]
+    fn every_matmul_kernel_agrees_on_checksum() {
+        let n = 67;
+        let (a, b) = matmul_inputs(n, 9);
+        let expected = reference(&a, &b).checksum();
+
+        let kernels = crate::KERNELS.iter().filter(|k| k.name().starts_with("matmul"));
+        for kernel in kernels {
+            let checksum = kernel.run_single(n, 9, &Control::NONE).unwrap().checksum;
+            assert!((checksum - expected).abs() <= 1e-12 * expected, "{}", kernel.name());
+        }
+        // Outro seed, outra entrada
+        assert_ne!(crate::kernels::find("matmul").unwrap().run_single(n, 10, &Control::NONE).unwrap().checksum, expected);
+    }
+
+    #[test]
+    fn checksum_notices_swapped_elements() {
+        let m = Matrix::from_fn(2, 2, |i, j| (i * 2 + j) as f64);
+        let swapped = Matrix::from_fn(2, 2, |i, j| m.get(j, i));
+        assert_eq!(m.as_slice().iter().sum::<f64>(), swapped.as_slice().iter().sum::<f64>());
+        assert_ne!(m.checksum(), swapped.checksum());
+    }
+}
@@ -0,0 +1,192 @@
+// --- MEMÓRIA ---
+// Quanto uma execução alocou. Duas fontes:
+//   CountingAlloc   alocador global que conta bytes/alocações por cima do alocador do sistema
+//                   (nativo e wasm). Só conta se o binário instalar:
+//                       #[global_allocator]
+//                       static ALLOC: core_algo::CountingAlloc = core_algo::CountingAlloc;
+//   memory_size     no wasm, o tamanho da memória linear (páginas de 64 KiB). Ela só cresce:
+//                   o crescimento mostra quanto o alocador teve de pedir a mais ao runtime.
+// Os contadores são do processo inteiro e o pico só tem um registro global, que cada medição
+// zera no começo. Com execuções sobrepostas (o backend roda até MAX_RUNNING, mais os jobs) uma
+// zeraria o pico da outra, e o de cada uma incluiria o que as outras alocaram: quem não mediu
+// sozinho do começo ao fim sai com `peak_bytes: None`. As alocações continuam contadas (também
+// somam as das outras execuções naquele intervalo).
+
+use serde::{Deserialize, Serialize};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+// Bytes vivos agora, o maior valor desde o último reset e o total de alocações
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+// Medições (MemoryProbe) em andamento e quantas já começaram, para saber se alguma se sobrepôs
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+static STARTED: AtomicU64 = AtomicU64::new(0);
+
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn grew(bytes: usize) {
+        let now = CURRENT.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        PEAK.fetch_max(now, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            INSTALLED.store(true, Ordering::Relaxed);
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            Self::grew(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            INSTALLED.store(true, Ordering::Relaxed);
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            Self::grew(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    // Um realloc conta como uma alocação (o Vec que cresce faz vários)
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            if new_size > layout.size() {
+                Self::grew(new_size - layout.size());
+            } else {
+                CURRENT.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+            }
+        }
+        new
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct MemoryStats {
+    // Maior quantidade de bytes alocados ao mesmo tempo, acima do que já estava alocado antes.
+    // None se outra execução mediu ao mesmo tempo (o pico seria dela também)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", tsify(optional))]
+    pub peak_bytes: Option<u64>,
+    // Alocações (e reallocs) por execução do kernel
+    pub allocations: u64,
+    // Só no wasm: tamanho da memória linear no fim e quanto ela cresceu durante o benchmark
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", tsify(optional))]
+    pub wasm_memory_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", tsify(optional))]
+    pub wasm_growth_bytes: Option<u64>,
+}
+
+#[cfg(target_arch = "wasm32")]
+fn linear_memory_bytes() -> Option<u64> {
+    Some(core::arch::wasm32::memory_size::<0>() as u64 * 65536)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn linear_memory_bytes() -> Option<u64> {
+    None
+}
+
+// Mede o que acontece entre `start` e `finish`
+pub struct MemoryProbe {
+    baseline: usize,
+    allocations: u64,
+    linear_memory: Option<u64>,
+    // Número desta medição em STARTED; None se já havia outra em andamento no start
+    alone: Option<u64>,
+}
+
+impl MemoryProbe {
+    pub fn start() -> Self {
+        let started = STARTED.fetch_add(1, Ordering::SeqCst) + 1;
+        let alone = (ACTIVE.fetch_add(1, Ordering::SeqCst) == 0).then_some(started);
+        let baseline = CURRENT.load(Ordering::Relaxed);
+        // Só quem está sozinho zera o pico: com outra em andamento ele é dela
+        if alone.is_some() {
+            PEAK.store(baseline, Ordering::Relaxed);
+        }
+        MemoryProbe {
+            baseline,
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            linear_memory: linear_memory_bytes(),
+            alone,
+        }
+    }
+
+    // `runs` execuções do kernel no meio (as alocações saem por execução).
+    // None se não há nada para contar: sem CountingAlloc e fora do wasm
+    pub fn finish(self, runs: usize) -> Option<MemoryStats> {
+        let linear_memory = linear_memory_bytes();
+        if !INSTALLED.load(Ordering::Relaxed) && linear_memory.is_none() {
+            return None;
+        }
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed) - self.allocations;
+        // Sozinha do começo ao fim: ninguém em andamento no start nem começou depois
+        let alone = self.alone == Some(STARTED.load(Ordering::SeqCst));
+        Some(MemoryStats {
+            peak_bytes: alone.then(|| PEAK.load(Ordering::Relaxed).saturating_sub(self.baseline) as u64),
+            allocations: allocations / runs.max(1) as u64,
+            wasm_memory_bytes: linear_memory,
+            wasm_growth_bytes: linear_memory.zip(self.linear_memory).map(|(end, start)| end - start),
+        })
+    }
+}
+
+// Também quando a execução é cancelada (a medição some sem `finish`)
+impl Drop for MemoryProbe {
+    fn drop(&mut self) {
+        ACTIVE.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bench_kernel, BenchConfig, Mode};
+
+    // O binário de testes do core-algo conta as alocações
+    #[global_allocator]
+    static ALLOC: CountingAlloc = CountingAlloc;
+
+    #[test]
+    fn results_report_peak_memory() {
+        let config = BenchConfig::new(Some(0), Some(2), None).unwrap();
+        // Os outros testes medem em paralelo: tenta até pegar uma execução sozinha
+        let memory = (0..200)
+            .map(|_| bench_kernel("matmul", 128, Mode::Single, config).unwrap().memory.expect("CountingAlloc instalado"))
+            .find(|m| m.peak_bytes.is_some())
+            .expect("alguma execução sem outra ao mesmo tempo");
+        // Pelo menos A, B e C (128x128 f64) vivas ao mesmo tempo
+        assert!(memory.peak_bytes.unwrap() >= 3 * 128 * 128 * 8, "{:?}", memory);
+        assert!(memory.allocations >= 3);
+        assert_eq!(memory.wasm_memory_bytes, None);
+    }
+
+    #[test]
+    fn overlapping_probes_have_no_peak() {
+        let first = MemoryProbe::start();
+        let second = MemoryProbe::start();
+        let big = vec![0u8; 1 << 20];
+        assert!(second.finish(1).unwrap().peak_bytes.is_none());
+        drop(big);
+        // A primeira também: a segunda começou no meio dela
+        assert!(first.finish(1).unwrap().peak_bytes.is_none());
+    }
+}
@@ -0,0 +1,151 @@
+use crate::harness::Stats;
+use crate::kernels::Precision;
+use crate::memory::MemoryStats;
+use crate::strassen::NumericError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// Resultado de uma execução: dá para comparar (JSON / objeto JS), não só exibir
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct BenchResult {
+    pub kernel: String,
+    pub size: usize,
+    // Seed das entradas: mesmo kernel + size + seed tem que dar o mesmo checksum em qualquer alvo
+    #[serde(default)]
+    pub seed: u64,
+    // Medido dentro do módulo (ver clock.rs), sem overhead de HTTP ou de cola JS
+    pub elapsed_ms: f64,
+    pub threads: usize,
+    pub target: String,
+    // Resumo da saída (na matmul, soma ponderada pela posição), para conferir o cálculo
+    pub checksum: f64,
+    // Operações na unidade do kernel (flops na matmul/fft, bytes no sha256/json, ...)
+    pub ops: u64,
+    // Bilhões de `ops` por segundo
+    pub gflops: f64,
+    // Tipo do elemento (só nos kernels que têm essa escolha, as matmul)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", tsify(optional))]
+    pub precision: Option<Precision>,
+    // Nas matmul recursivas: o cutoff usado e o erro contra a matmul O(n³)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", tsify(optional))]
+    pub cutoff: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", tsify(optional))]
+    pub numeric_error: Option<NumericError>,
+    // Só quando veio do harness (aquecimento + repetições); aí elapsed_ms é a mediana
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", tsify(optional))]
+    pub stats: Option<Stats>,
+    // Pico de memória e alocações (ver memory.rs); None se o binário não conta alocações
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", tsify(optional))]
+    pub memory: Option<MemoryStats>,
+}
+
+impl BenchResult {
+    pub fn new(kernel: &str, size: usize, seed: u64, elapsed_ms: f64, threads: usize, checksum: f64, ops: u64) -> Self {
+        BenchResult {
+            kernel: kernel.to_string(),
+            size,
+            seed,
+            elapsed_ms,
+            threads,
+            target: TARGET.to_string(),
+            checksum,
+            ops,
+            gflops: if elapsed_ms > 0.0 { ops as f64 / (elapsed_ms / 1000.0) / 1e9 } else { 0.0 },
+            precision: None,
+            cutoff: None,
+            numeric_error: None,
+            stats: None,
+            memory: None,
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub const TARGET: &str = "native";
+#[cfg(all(target_arch = "wasm32", not(target_os = "wasi")))]
+pub const TARGET: &str = "wasm32";
+// Build WASI, rodando no servidor dentro do wasmtime
+#[cfg(all(target_arch = "wasm32", target_os = "wasi"))]
+pub const TARGET: &str = "wasm32-wasi";
+
+const MIB: f64 = 1024.0 * 1024.0;
+
+// O texto de sempre, para quem só quer mostrar na tela
+impl fmt::Display for BenchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let wasm = self.target.starts_with("wasm32");
+        let mode = if self.target == crate::verify::GPU_TARGET {
+            "WebGPU".to_string()
+        } else if wasm && self.threads <= 1 {
+            "WASM Single-Core".to_string()
+        } else if wasm {
+            format!("WASM MT - {} threads", self.threads)
+        } else {
+            format!("{} - {} cores", if self.threads > 1 { "MT" } else { "ST" }, self.threads)
+        };
+        let precision = self.precision.map_or(String::new(), |p| format!(" {}", p));
+        let what = if self.kernel == "matmul" {
+            format!("matriz {}x{}{}", self.size, self.size, precision)
+        } else if let Some(variant) = self.kernel.strip_prefix("matmul-") {
+            let cutoff = self.cutoff.map_or(String::new(), |c| format!(", cutoff {}", c));
+            format!("matriz {}x{}{} ({}{})", self.size, self.size, precision, variant, cutoff)
+        } else {
+            format!("{} (size {})", self.kernel, self.size)
+        };
+        write!(
+            f,
+            "Processado {} em {:.2}ms ({}, {:.2} G-ops/s)",
+            what,
+            // Direto em ms: o resultado pode vir de fora (browser, histórico) com NaN ou negativo,
+            // e o Duration entraria em pânico
+            self.elapsed_ms,
+            mode,
+            self.gflops,
+        )?;
+        if let Some(stats) = &self.stats {
+            write!(
+                f,
+                " [mediana de {}, min {:.2}ms, p95 {:.2}ms, ±{:.2}ms IC95]",
+                stats.reps,
+                stats.min,
+                stats.p95,
+                (stats.ci95_high - stats.ci95_low) / 2.0,
+            )?;
+        }
+        if let Some(memory) = &self.memory {
+            match memory.peak_bytes {
+                Some(peak) => write!(f, " [pico {:.1} MiB, {} alocações", peak as f64 / MIB, memory.allocations)?,
+                None => write!(f, " [{} alocações", memory.allocations)?,
+            }
+            if let (Some(total), Some(growth)) = (memory.wasm_memory_bytes, memory.wasm_growth_bytes) {
+                write!(f, ", memória linear {:.1} MiB (+{:.1})", total as f64 / MIB, growth as f64 / MIB)?;
+            }
+            write!(f, "]")?;
+        }
+        if let Some(error) = &self.numeric_error {
+            write!(f, " [erro relativo {:.2e}, máximo {:.2e}]", error.relative, error.max_abs)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_accepts_any_elapsed_ms() {
+        let mut result = BenchResult::new("sieve", 1000, 42, 1.5, 1, 168.0, 1000);
+        assert!(result.to_string().contains(" em 1.50ms "), "{}", result);
+        for elapsed_ms in [-1.0, f64::NAN, f64::INFINITY] {
+            result.elapsed_ms = elapsed_ms;
+            assert!(result.to_string().starts_with("Processado "));
+        }
+    }
+}
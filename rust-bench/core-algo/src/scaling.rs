@@ -0,0 +1,124 @@
+// --- THREADS E ESCALABILIDADE (nativo) ---
+// O pool global do rayon usa todos os núcleos. Aqui o kernel roda num ThreadPool
+// próprio com o número de threads pedido, e o `sweep` repete isso de 1 até N threads
+// para montar a curva de strong scaling (mesmo problema, mais threads):
+//   speedup(n)    = tempo(1) / tempo(n)
+//   eficiência(n) = speedup(n) / n
+
+use crate::harness::{bench_kernel, BenchConfig};
+use crate::{BenchResult, Mode};
+use serde::{Deserialize, Serialize};
+
+pub const MAX_THREADS: usize = 256;
+
+// Só a faixa de `threads`, sem subir pool nenhum (para recusar antes de gastar threads do SO)
+pub fn check_threads(threads: usize) -> Result<(), String> {
+    if threads == 0 || threads > MAX_THREADS {
+        return Err(format!("threads deve estar entre 1 e {}", MAX_THREADS));
+    }
+    Ok(())
+}
+
+// Pool dedicado de `threads` threads; Err se o número é inválido ou o pool não sobe
+pub fn thread_pool(threads: usize) -> Result<rayon::ThreadPool, String> {
+    check_threads(threads)?;
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .map_err(|e| format!("pool de {} threads: {}", threads, e))
+}
+
+// Roda em Mode::Parallel dentro de um pool de `threads` threads.
+// Ok(None) se o kernel não existe; Err se o número de threads é inválido ou o pool não sobe
+pub fn bench_with_threads(
+    name: &str,
+    size: usize,
+    config: BenchConfig,
+    threads: usize,
+) -> Result<Option<BenchResult>, String> {
+    let pool = thread_pool(threads)?;
+    // Dentro do install, current_num_threads() (e o `threads` do resultado) é o tamanho do pool
+    Ok(pool.install(|| bench_kernel(name, size, Mode::Parallel, config)))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ScalingPoint {
+    pub threads: usize,
+    // Mediana das repetições
+    pub elapsed_ms: f64,
+    pub speedup: f64,
+    pub efficiency: f64,
+    pub checksum: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Scaling {
+    pub kernel: String,
+    pub size: usize,
+    pub seed: u64,
+    pub warmup: usize,
+    pub reps: usize,
+    pub points: Vec<ScalingPoint>,
+}
+
+// 1..=max_threads threads, uma medição (com aquecimento/repetições) para cada
+pub fn sweep(name: &str, size: usize, config: BenchConfig, max_threads: usize) -> Result<Option<Scaling>, String> {
+    if max_threads == 0 || max_threads > MAX_THREADS {
+        return Err(format!("max_threads deve estar entre 1 e {}", MAX_THREADS));
+    }
+
+    let mut points: Vec<ScalingPoint> = Vec::with_capacity(max_threads);
+    for threads in 1..=max_threads {
+        let Some(result) = bench_with_threads(name, size, config, threads)? else {
+            return Ok(None);
+        };
+        let baseline = points.first().map_or(result.elapsed_ms, |p| p.elapsed_ms);
+        let speedup = if result.elapsed_ms > 0.0 { baseline / result.elapsed_ms } else { 0.0 };
+        points.push(ScalingPoint {
+            threads,
+            elapsed_ms: result.elapsed_ms,
+            speedup,
+            efficiency: speedup / threads as f64,
+            checksum: result.checksum,
+        });
+    }
+
+    Ok(Some(Scaling {
+        kernel: name.to_string(),
+        size,
+        seed: config.seed,
+        warmup: config.warmup,
+        reps: config.reps,
+        points,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_in_pool_of_requested_size() {
+        let config = BenchConfig::new(Some(0), Some(1), None).unwrap();
+        let result = bench_with_threads("sieve", 10_000, config, 3).unwrap().unwrap();
+        assert_eq!(result.threads, 3);
+        assert!(bench_with_threads("sieve", 10, config, 0).is_err());
+        assert!(bench_with_threads("nao-existe", 10, config, 1).unwrap().is_none());
+    }
+
+    #[test]
+    fn sweep_reports_speedup_against_one_thread() {
+        let config = BenchConfig::new(Some(0), Some(1), None).unwrap();
+        let scaling = sweep("matmul-ikj", 64, config, 3).unwrap().unwrap();
+        let threads: Vec<usize> = scaling.points.iter().map(|p| p.threads).collect();
+        assert_eq!(threads, [1, 2, 3]);
+
+        let first = &scaling.points[0];
+        assert_eq!((first.speedup, first.efficiency), (1.0, 1.0));
+        for p in &scaling.points {
+            // Mesma conta em qualquer número de threads
+            assert_eq!(p.checksum, first.checksum);
+            assert!((p.efficiency - p.speedup / p.threads as f64).abs() < 1e-12);
+        }
+    }
+}
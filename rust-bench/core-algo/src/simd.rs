@@ -0,0 +1,213 @@
+// --- SIMD EXPLÍCITO ---
+// O laço interno da matmul i-k-j é um "axpy": row[j] += a * b[j].
+// Aqui ele é escrito com intrínsecos de cada alvo, em f64 e em f32:
+//   x86_64 -> AVX2 (4 f64 / 8 f32 por vez) se a CPU tiver, senão SSE2 (2 f64 / 4 f32, sempre presente)
+//   wasm32 -> simd128 (2 f64 / 4 f32), só se compilado com -C target-feature=+simd128
+//   outros -> escalar
+// Os inteiros ficam no escalar (o compilador vetoriza sozinho o que der).
+// Multiplicação e soma continuam separadas (sem FMA), então o resultado é o mesmo do escalar.
+
+use crate::matrix::Element;
+
+// row[j] += a * b[j], para j em 0..row.len()
+pub(crate) type Axpy<T> = fn(&mut [T], T, &[T]);
+
+pub(crate) fn axpy_scalar<T: Element>(row: &mut [T], a: T, b: &[T]) {
+    for (value, &b_j) in row.iter_mut().zip(b) {
+        *value += a * b_j;
+    }
+}
+
+// Escolhe a melhor versão uma vez (a detecção em tempo de execução fica fora do laço)
+pub(crate) fn axpy() -> Axpy<f64> {
+    select().1
+}
+
+pub(crate) fn axpy_f32() -> Axpy<f32> {
+    select().2
+}
+
+// Nome do caminho usado nesta máquina/build ("avx2", "sse2", "simd128" ou "scalar")
+pub fn backend() -> &'static str {
+    select().0
+}
+
+type Paths = (&'static str, Axpy<f64>, Axpy<f32>);
+
+#[cfg(target_arch = "x86_64")]
+fn select() -> Paths {
+    if is_x86_feature_detected!("avx2") {
+        ("avx2", x86::axpy_avx2, x86::axpy_avx2_f32)
+    } else {
+        ("sse2", x86::axpy_sse2, x86::axpy_sse2_f32)
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+fn select() -> Paths {
+    ("simd128", wasm::axpy_simd128, wasm::axpy_simd128_f32)
+}
+
+#[cfg(not(any(target_arch = "x86_64", all(target_arch = "wasm32", target_feature = "simd128"))))]
+fn select() -> Paths {
+    ("scalar", axpy_scalar, axpy_scalar)
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use std::arch::x86_64::*;
+
+    // Só é devolvida por select() depois de is_x86_feature_detected!("avx2")
+    pub(super) fn axpy_avx2(row: &mut [f64], a: f64, b: &[f64]) {
+        debug_assert!(is_x86_feature_detected!("avx2"));
+        // SAFETY: select() (e available(), nos testes) só devolve esta função com AVX2 detectado
+        unsafe { avx2(row, a, b) }
+    }
+
+    // SSE2 faz parte do x86_64 base, não precisa de detecção
+    pub(super) fn axpy_sse2(row: &mut [f64], a: f64, b: &[f64]) {
+        let n = row.len().min(b.len());
+        let split = n - n % 2;
+        // SAFETY: SSE2 é base do x86_64; j + 2 <= split <= n = min(row.len(), b.len()), então
+        // cada load/store (sem alinhamento) fica dentro de row e de b
+        unsafe {
+            let va = _mm_set1_pd(a);
+            for j in (0..split).step_by(2) {
+                let r = row.as_mut_ptr().add(j);
+                let vb = _mm_loadu_pd(b.as_ptr().add(j));
+                _mm_storeu_pd(r, _mm_add_pd(_mm_loadu_pd(r), _mm_mul_pd(va, vb)));
+            }
+        }
+        super::axpy_scalar(&mut row[split..n], a, &b[split..n]);
+    }
+
+    pub(super) fn axpy_sse2_f32(row: &mut [f32], a: f32, b: &[f32]) {
+        let n = row.len().min(b.len());
+        let split = n - n % 4;
+        // SAFETY: SSE2 é base do x86_64; j + 4 <= split <= n = min(row.len(), b.len()), então
+        // cada load/store (sem alinhamento) fica dentro de row e de b
+        unsafe {
+            let va = _mm_set1_ps(a);
+            for j in (0..split).step_by(4) {
+                let r = row.as_mut_ptr().add(j);
+                let vb = _mm_loadu_ps(b.as_ptr().add(j));
+                _mm_storeu_ps(r, _mm_add_ps(_mm_loadu_ps(r), _mm_mul_ps(va, vb)));
+            }
+        }
+        super::axpy_scalar(&mut row[split..n], a, &b[split..n]);
+    }
+
+    // Mesma condição da axpy_avx2
+    pub(super) fn axpy_avx2_f32(row: &mut [f32], a: f32, b: &[f32]) {
+        debug_assert!(is_x86_feature_detected!("avx2"));
+        // SAFETY: select() (e available(), nos testes) só devolve esta função com AVX2 detectado
+        unsafe { avx2_f32(row, a, b) }
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn avx2_f32(row: &mut [f32], a: f32, b: &[f32]) {
+        let n = row.len().min(b.len());
+        let split = n - n % 8;
+        let va = _mm256_set1_ps(a);
+        // SAFETY (dos ponteiros): j + 8 <= split <= n = min(row.len(), b.len())
+        for j in (0..split).step_by(8) {
+            let r = row.as_mut_ptr().add(j);
+            let vb = _mm256_loadu_ps(b.as_ptr().add(j));
+            _mm256_storeu_ps(r, _mm256_add_ps(_mm256_loadu_ps(r), _mm256_mul_ps(va, vb)));
+        }
+        super::axpy_scalar(&mut row[split..n], a, &b[split..n]);
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn avx2(row: &mut [f64], a: f64, b: &[f64]) {
+        let n = row.len().min(b.len());
+        let split = n - n % 4;
+        let va = _mm256_set1_pd(a);
+        // SAFETY (dos ponteiros): j + 4 <= split <= n = min(row.len(), b.len())
+        for j in (0..split).step_by(4) {
+            let r = row.as_mut_ptr().add(j);
+            let vb = _mm256_loadu_pd(b.as_ptr().add(j));
+            _mm256_storeu_pd(r, _mm256_add_pd(_mm256_loadu_pd(r), _mm256_mul_pd(va, vb)));
+        }
+        super::axpy_scalar(&mut row[split..n], a, &b[split..n]);
+    }
+
+    #[cfg(test)]
+    pub(super) fn available() -> Vec<super::Paths> {
+        let mut paths: Vec<super::Paths> = vec![("sse2", axpy_sse2, axpy_sse2_f32)];
+        if is_x86_feature_detected!("avx2") {
+            paths.push(("avx2", axpy_avx2, axpy_avx2_f32));
+        }
+        paths
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+mod wasm {
+    use core::arch::wasm32::*;
+
+    pub(super) fn axpy_simd128(row: &mut [f64], a: f64, b: &[f64]) {
+        let n = row.len().min(b.len());
+        let split = n - n % 2;
+        let va = f64x2_splat(a);
+        for j in (0..split).step_by(2) {
+            // SAFETY: j + 2 <= split <= n = min(row.len(), b.len()), então os 16 bytes lidos
+            // e escritos ficam dentro de row e de b; v128_load/store aceitam ponteiro desalinhado
+            unsafe {
+                let r = row.as_mut_ptr().add(j) as *mut v128;
+                let vb = v128_load(b.as_ptr().add(j) as *const v128);
+                v128_store(r, f64x2_add(v128_load(r), f64x2_mul(va, vb)));
+            }
+        }
+        super::axpy_scalar(&mut row[split..n], a, &b[split..n]);
+    }
+
+    pub(super) fn axpy_simd128_f32(row: &mut [f32], a: f32, b: &[f32]) {
+        let n = row.len().min(b.len());
+        let split = n - n % 4;
+        let va = f32x4_splat(a);
+        for j in (0..split).step_by(4) {
+            // SAFETY: j + 4 <= split <= n = min(row.len(), b.len()), então os 16 bytes lidos
+            // e escritos ficam dentro de row e de b; v128_load/store aceitam ponteiro desalinhado
+            unsafe {
+                let r = row.as_mut_ptr().add(j) as *mut v128;
+                let vb = v128_load(b.as_ptr().add(j) as *const v128);
+                v128_store(r, f32x4_add(v128_load(r), f32x4_mul(va, vb)));
+            }
+        }
+        super::axpy_scalar(&mut row[split..n], a, &b[split..n]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Mesmas operações na mesma ordem: o SIMD tem que dar exatamente o escalar
+    fn check<T: Element>(name: &str, f: Axpy<T>) {
+        // Tamanhos com e sem "sobra" depois dos blocos de 2/4/8
+        for n in [0, 1, 2, 3, 4, 5, 7, 8, 9, 15, 63, 64, 65] {
+            let b: Vec<T> = (0..n).map(|j| T::from_unit((j % 10) as f64 * 0.037)).collect();
+            let mut expected: Vec<T> = (0..n).map(|j| T::from_unit(1.0 / (j as f64 + 2.0))).collect();
+            let start = expected.clone();
+            let a = T::from_unit(0.75);
+            axpy_scalar(&mut expected, a, &b);
+
+            let mut row = start.clone();
+            f(&mut row, a, &b);
+            assert_eq!(row, expected, "{} {:?} n={}", name, T::PRECISION, n);
+        }
+    }
+
+    #[test]
+    fn simd_paths_match_scalar() {
+        let mut paths = vec![select()];
+        #[cfg(target_arch = "x86_64")]
+        paths.extend(x86::available());
+
+        for (name, f64_path, f32_path) in paths {
+            check(name, f64_path);
+            check(name, f32_path);
+        }
+    }
+}
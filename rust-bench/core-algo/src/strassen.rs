@@ -0,0 +1,260 @@
+// --- MATMUL RECURSIVA: DIVIDE E CONQUISTA E STRASSEN ---
+// Divide A, B e C em quatro quadrantes e multiplica os quadrantes recursivamente até o
+// lado chegar no `cutoff`; daí para baixo quem multiplica é a variante Simd (matrix.rs).
+//   Blocks    8 produtos por nível: as mesmas contas do O(n³), só em outra ordem
+//   Strassen  7 produtos por nível (O(n^2.81)), em troca de 18 somas/subtrações de
+//             quadrantes e de mais erro de arredondamento nos floats
+// Lado ímpar vira par com uma linha/coluna de zeros no quadrante (a sobra é descartada
+// na hora de montar C). Os produtos de um nível rodam com rayon::join no nativo (e no
+// wasm com a feature wasm-threads); no wasm sem threads, um depois do outro.
+
+use crate::control::{Cancelled, Control, Progress};
+use crate::matrix::{matmul, Element, MatMulVariant, Matrix};
+use crate::Mode;
+use serde::{Deserialize, Serialize};
+
+// Lado a partir do qual o kernel para de dividir
+pub const DEFAULT_CUTOFF: usize = 64;
+// Menor cutoff aceito pelos kernels: com i32 os produtos intermediários do Strassen
+// crescem ~81·N²/cutoff e precisam caber no i32 até o max_size (8192)
+pub const MIN_CUTOFF: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recursion {
+    Blocks,
+    Strassen,
+}
+
+impl Recursion {
+    // Quantos produtos de quadrantes cada nível faz
+    fn branches(self) -> usize {
+        match self {
+            Recursion::Blocks => 8,
+            Recursion::Strassen => 7,
+        }
+    }
+}
+
+// Diferença entre um resultado e o da matmul O(n³) (mesma ordem de soma do i-j-k ingênuo)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct NumericError {
+    // Maior |c - referência| entre os elementos
+    pub max_abs: f64,
+    // ||c - referência|| / ||referência|| (norma de Frobenius)
+    pub relative: f64,
+}
+
+impl NumericError {
+    pub fn between<T: Element>(result: &Matrix<T>, reference: &Matrix<T>) -> Self {
+        let (diff, norm) = result
+            .as_slice()
+            .iter()
+            .zip(reference.as_slice())
+            .map(|(x, r)| (x.to_f64() - r.to_f64(), r.to_f64()))
+            .fold((0.0, 0.0), |(diff, norm), (d, r)| (diff + d * d, norm + r * r));
+        NumericError {
+            max_abs: result.max_abs_diff(reference),
+            relative: if norm > 0.0 { (diff / norm).sqrt() } else { diff.sqrt() },
+        }
+    }
+}
+
+// Quantos produtos de lado <= cutoff a recursão vai fazer (as unidades do progresso)
+fn leaves(n: usize, cutoff: usize, recursion: Recursion) -> usize {
+    if n <= cutoff {
+        1
+    } else {
+        recursion.branches() * leaves(n.div_ceil(2), cutoff, recursion)
+    }
+}
+
+struct Plan<'p> {
+    recursion: Recursion,
+    cutoff: usize,
+    parallel: bool,
+    progress: &'p Progress<'p>,
+}
+
+impl Plan<'_> {
+    fn join<A, B, RA, RB>(&self, a: A, b: B) -> (RA, RB)
+    where
+        A: FnOnce() -> RA + Send,
+        B: FnOnce() -> RB + Send,
+        RA: Send,
+        RB: Send,
+    {
+        if self.parallel && cfg!(not(all(target_arch = "wasm32", not(feature = "wasm-threads")))) {
+            rayon::join(a, b)
+        } else {
+            (a(), b())
+        }
+    }
+}
+
+// C = A * B para matrizes quadradas do mesmo lado
+pub fn recursive_matmul<T: Element>(
+    a: &Matrix<T>,
+    b: &Matrix<T>,
+    recursion: Recursion,
+    cutoff: usize,
+    mode: Mode,
+) -> Matrix<T> {
+    recursive_matmul_with(a, b, recursion, cutoff, mode, &Control::NONE).expect("sem token de cancelamento")
+}
+
+// Igual a `recursive_matmul`, avisando o progresso e checando o cancelamento a cada produto no cutoff
+pub fn recursive_matmul_with<T: Element>(
+    a: &Matrix<T>,
+    b: &Matrix<T>,
+    recursion: Recursion,
+    cutoff: usize,
+    mode: Mode,
+    ctl: &Control,
+) -> Result<Matrix<T>, Cancelled> {
+    let n = a.rows();
+    assert!(n == a.cols() && (n, n) == (b.rows(), b.cols()), "matrizes quadradas do mesmo lado");
+    assert!(cutoff >= 1, "cutoff tem que ser pelo menos 1");
+    let progress = ctl.progress(leaves(n, cutoff, recursion));
+    let plan = Plan {
+        recursion,
+        cutoff,
+        parallel: mode == Mode::Parallel,
+        progress: &progress,
+    };
+    multiply(a, b, &plan)
+}
+
+fn multiply<T: Element>(a: &Matrix<T>, b: &Matrix<T>, plan: &Plan) -> Result<Matrix<T>, Cancelled> {
+    let n = a.rows();
+    if n <= plan.cutoff {
+        let c = matmul(a, b, MatMulVariant::Simd, Mode::Single);
+        plan.progress.advance(1)?;
+        return Ok(c);
+    }
+
+    let h = n.div_ceil(2);
+    let [a11, a12, a21, a22] = quadrants(a, h);
+    let [b11, b12, b21, b22] = quadrants(b, h);
+    let quadrants = match plan.recursion {
+        Recursion::Blocks => {
+            // C11 = A11·B11 + A12·B21, e assim por diante: cada quadrante de C numa tarefa
+            let pair = |x1: &Matrix<T>, y1: &Matrix<T>, x2: &Matrix<T>, y2: &Matrix<T>| {
+                Ok::<_, Cancelled>(add(&multiply(x1, y1, plan)?, &multiply(x2, y2, plan)?))
+            };
+            let ((c11, c12), (c21, c22)) = plan.join(
+                || plan.join(|| pair(&a11, &b11, &a12, &b21), || pair(&a11, &b12, &a12, &b22)),
+                || plan.join(|| pair(&a21, &b11, &a22, &b21), || pair(&a21, &b12, &a22, &b22)),
+            );
+            [c11?, c12?, c21?, c22?]
+        }
+        Recursion::Strassen => {
+            let ((m1, m2), (m3, m4)) = plan.join(
+                || {
+                    plan.join(
+                        || multiply(&add(&a11, &a22), &add(&b11, &b22), plan),
+                        || multiply(&add(&a21, &a22), &b11, plan),
+                    )
+                },
+                || plan.join(|| multiply(&a11, &sub(&b12, &b22), plan), || multiply(&a22, &sub(&b21, &b11), plan)),
+            );
+            let ((m5, m6), m7) = plan.join(
+                || {
+                    plan.join(
+                        || multiply(&add(&a11, &a12), &b22, plan),
+                        || multiply(&sub(&a21, &a11), &add(&b11, &b12), plan),
+                    )
+                },
+                || multiply(&sub(&a12, &a22), &add(&b21, &b22), plan),
+            );
+            let (m1, m2, m3, m4, m5, m6, m7) = (m1?, m2?, m3?, m4?, m5?, m6?, m7?);
+            [
+                add(&sub(&add(&m1, &m4), &m5), &m7),
+                add(&m3, &m5),
+                add(&m2, &m4),
+                add(&add(&sub(&m1, &m2), &m3), &m6),
+            ]
+        }
+    };
+    Ok(assemble(n, h, &quadrants))
+}
+
+// Os quatro quadrantes h x h (11, 12, 21, 22), com zeros onde passam da borda
+fn quadrants<T: Element>(m: &Matrix<T>, h: usize) -> [Matrix<T>; 4] {
+    let n = m.rows();
+    let quadrant = |top: usize, left: usize| {
+        let mut q = Matrix::zeros(h, h);
+        let cols = h.min(n - left);
+        for (i, row) in q.as_mut_slice().chunks_mut(h).take(n - top).enumerate() {
+            row[..cols].copy_from_slice(&m.row(top + i)[left..left + cols]);
+        }
+        q
+    };
+    [quadrant(0, 0), quadrant(0, h), quadrant(h, 0), quadrant(h, h)]
+}
+
+// C n x n a partir dos quadrantes h x h, descartando a sobra do preenchimento
+fn assemble<T: Element>(n: usize, h: usize, quadrants: &[Matrix<T>; 4]) -> Matrix<T> {
+    let mut c = Matrix::zeros(n, n);
+    for (i, row) in c.as_mut_slice().chunks_mut(n).enumerate() {
+        let (top, bottom) = if i < h { (&quadrants[0], &quadrants[1]) } else { (&quadrants[2], &quadrants[3]) };
+        let r = i % h;
+        row[..h].copy_from_slice(top.row(r));
+        row[h..].copy_from_slice(&bottom.row(r)[..n - h]);
+    }
+    c
+}
+
+fn add<T: Element>(x: &Matrix<T>, y: &Matrix<T>) -> Matrix<T> {
+    zip(x, y, |a, b| a + b)
+}
+
+fn sub<T: Element>(x: &Matrix<T>, y: &Matrix<T>) -> Matrix<T> {
+    zip(x, y, |a, b| a - b)
+}
+
+fn zip<T: Element>(x: &Matrix<T>, y: &Matrix<T>, f: impl Fn(T, T) -> T) -> Matrix<T> {
+    let mut out = x.clone();
+    for (o, &v) in out.as_mut_slice().iter_mut().zip(y.as_slice()) {
+        *o = f(*o, v);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::matmul_inputs_as;
+
+    #[test]
+    fn recursion_matches_classic_matmul() {
+        // Lados ímpares em vários níveis (70 -> 35 -> 18 -> 9) e um que nem chega a dividir
+        for n in [1, 9, 70, 128] {
+            let (a, b) = matmul_inputs_as::<f64>(n, 3);
+            let expected = matmul(&a, &b, MatMulVariant::Tiled, Mode::Single);
+            for recursion in [Recursion::Blocks, Recursion::Strassen] {
+                for mode in [Mode::Single, Mode::Parallel] {
+                    let c = recursive_matmul(&a, &b, recursion, 16, mode);
+                    let error = NumericError::between(&c, &expected);
+                    assert!(error.relative < 1e-13, "{:?} {:?} n={} {:?}", recursion, mode, n, error);
+                }
+            }
+        }
+
+        // Inteiros: a conta é exata
+        let (a, b) = matmul_inputs_as::<i32>(70, 3);
+        let expected = matmul(&a, &b, MatMulVariant::Tiled, Mode::Single);
+        assert_eq!(recursive_matmul(&a, &b, Recursion::Strassen, 16, Mode::Parallel), expected);
+    }
+
+    #[test]
+    fn strassen_error_grows_in_f32() {
+        let (a, b) = matmul_inputs_as::<f32>(256, 1);
+        let expected = matmul(&a, &b, MatMulVariant::Tiled, Mode::Single);
+        let blocks = NumericError::between(&recursive_matmul(&a, &b, Recursion::Blocks, 16, Mode::Single), &expected);
+        let strassen = NumericError::between(&recursive_matmul(&a, &b, Recursion::Strassen, 16, Mode::Single), &expected);
+        assert!(strassen.relative > 0.0 && strassen.relative < 1e-4, "{:?}", strassen);
+        assert!(strassen.max_abs > blocks.max_abs, "{:?} x {:?}", strassen, blocks);
+        assert_eq!(leaves(256, 16, Recursion::Strassen), 7usize.pow(4));
+    }
+}
@@ -0,0 +1,166 @@
+// --- VERIFICAÇÃO ENTRE ALVOS ---
+// Mesmo kernel + size + seed tem que dar o mesmo checksum no nativo, no wasm (browser
+// ou wasmtime) e na GPU. Cada resultado tem uma tolerância de acordo com a precisão:
+//   f64 (CPU, qualquer alvo)  -> 1e-9 relativo (só ordem de soma / libm diferente)
+//   f32 (GPU ou CPU em f32)   -> 1e-3 relativo (acumula N produtos em f32)
+//   i32 / i64                 -> exato
+// Resultado sem `precision` (kernels sem essa escolha, histórico antigo) vai pelo alvo: GPU é f32.
+
+use crate::kernels::Precision;
+use crate::BenchResult;
+use serde::{Deserialize, Serialize};
+
+// `target` dos resultados que vêm da GPU (gpu-bench, WebGPU)
+pub const GPU_TARGET: &str = "webgpu";
+
+// Tolerância relativa do checksum para um resultado
+pub fn tolerance(result: &BenchResult) -> f64 {
+    match result.precision {
+        Some(p) if p.is_integer() => 0.0,
+        Some(Precision::F32) => 1e-3,
+        Some(_) => 1e-9,
+        None if result.target == GPU_TARGET => 1e-3,
+        None => 1e-9,
+    }
+}
+
+// Inteiros partem de outra entrada (0..=9): só se comparam com inteiros
+fn integer(result: &BenchResult) -> bool {
+    result.precision.is_some_and(Precision::is_integer)
+}
+
+// Todas as variantes de matmul (e a GPU) fazem a mesma conta
+fn family(kernel: &str) -> &str {
+    if kernel.starts_with("matmul") {
+        "matmul"
+    } else {
+        kernel
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct Check {
+    pub kernel: String,
+    pub target: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", tsify(optional))]
+    pub precision: Option<Precision>,
+    pub checksum: f64,
+    // |checksum - referência| / |referência|
+    pub rel_error: f64,
+    pub tolerance: f64,
+    pub ok: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ts", derive(tsify::Tsify))]
+pub struct Verification {
+    pub kernel: String,
+    pub size: usize,
+    pub seed: u64,
+    // Checksum do primeiro resultado (de preferência o nativo)
+    pub reference: f64,
+    pub ok: bool,
+    pub checks: Vec<Check>,
+}
+
+// Compara todos os resultados com o primeiro. Erro se não forem da mesma conta
+// (kernel, size ou seed diferentes), porque aí comparar checksum não quer dizer nada
+pub fn verify(results: &[BenchResult]) -> Result<Verification, String> {
+    let first = results.first().ok_or("nenhum resultado para verificar")?;
+    if let Some(other) = results.iter().find(|r| {
+        family(&r.kernel) != family(&first.kernel) || r.size != first.size || r.seed != first.seed
+    }) {
+        return Err(format!(
+            "resultados não comparáveis: {} size {} seed {} x {} size {} seed {}",
+            first.kernel, first.size, first.seed, other.kernel, other.size, other.seed
+        ));
+    }
+    if results.iter().any(|r| integer(r) != integer(first)) {
+        return Err("resultados não comparáveis: inteiros (i32/i64) só com inteiros".to_string());
+    }
+
+    let reference = first.checksum;
+    let checks: Vec<Check> = results
+        .iter()
+        .map(|r| {
+            let rel_error = if reference == r.checksum {
+                0.0
+            } else {
+                (r.checksum - reference).abs() / reference.abs().max(f64::MIN_POSITIVE)
+            };
+            // Vale a tolerância do mais impreciso dos dois
+            let tolerance = tolerance(r).max(tolerance(first));
+            Check {
+                kernel: r.kernel.clone(),
+                target: r.target.clone(),
+                precision: r.precision,
+                checksum: r.checksum,
+                rel_error,
+                tolerance,
+                ok: rel_error <= tolerance,
+            }
+        })
+        .collect();
+
+    Ok(Verification {
+        kernel: first.kernel.clone(),
+        size: first.size,
+        seed: first.seed,
+        reference,
+        ok: checks.iter().all(|c| c.ok),
+        checks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(kernel: &str, target: &str, checksum: f64) -> BenchResult {
+        let mut r = BenchResult::new(kernel, 64, 7, 1.0, 1, checksum, 1);
+        r.target = target.to_string();
+        r
+    }
+
+    #[test]
+    fn tolerance_depends_on_precision() {
+        let native = result("matmul", "native", 1000.0);
+        let wasm = result("matmul-tiled", "wasm32", 1000.0 * (1.0 + 1e-12));
+        let gpu = result("matmul", GPU_TARGET, 1000.0 * (1.0 + 1e-4));
+        let v = verify(&[native.clone(), wasm, gpu]).unwrap();
+        assert!(v.ok, "{:?}", v);
+
+        // 1e-4 de diferença passa para a GPU, mas não entre dois alvos f64
+        let wrong = result("matmul", "wasm32", 1000.0 * (1.0 + 1e-4));
+        let v = verify(&[native.clone(), wrong]).unwrap();
+        assert!(!v.ok);
+        assert!(!v.checks[1].ok);
+
+        // A CPU em f32 tem a folga da GPU; inteiros têm que bater exato
+        let mut cpu_f32 = result("matmul-simd", "native", 1000.0 * (1.0 + 1e-4));
+        cpu_f32.precision = Some(Precision::F32);
+        assert!(verify(&[native, cpu_f32]).unwrap().ok);
+        let mut i32 = result("matmul", "native", 500.0);
+        i32.precision = Some(Precision::I32);
+        let mut i64 = result("matmul-tiled", "wasm32", 500.0);
+        i64.precision = Some(Precision::I64);
+        assert!(verify(&[i32.clone(), i64.clone()]).unwrap().ok);
+        i64.checksum += 1.0;
+        assert!(!verify(&[i32, i64]).unwrap().ok);
+    }
+
+    #[test]
+    fn refuses_different_inputs() {
+        let a = result("matmul", "native", 1.0);
+        let mut b = result("matmul", "wasm32", 1.0);
+        b.seed = 8;
+        assert!(verify(&[a.clone(), b]).is_err());
+        assert!(verify(&[a.clone(), result("sort", "wasm32", 1.0)]).is_err());
+        let mut int = result("matmul", "wasm32", 1.0);
+        int.precision = Some(Precision::I32);
+        assert!(verify(&[a, int]).is_err());
+        assert!(verify(&[]).is_err());
+    }
+}
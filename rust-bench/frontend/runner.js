@@ -1,5 +1,7 @@
 // frontend/runner.js
-const { run_wasm_bench } = require('./pkg/wasm_frontend.js');
+// Tipos do pkg/wasm_frontend.d.ts (wasm-bindgen --target nodejs --out-dir pkg ...)
+// @ts-check
+const { run_wasm_bench, run_bench } = require('./pkg/wasm_frontend.js');
 
 const SIZE = 2000; // Mesmo tamanho do teste nativo
 
@@ -10,5 +12,17 @@ const start = performance.now();
 const result = run_wasm_bench(SIZE);
 const end = performance.now();
 
-console.log(`Resultado: ${result}`);
-console.log(`Tempo Total (JS + WASM): ${((end - start) / 1000).toFixed(4)}s`);
\ No newline at end of file
+console.log(`Resultado: ${result.formatted}`);
+console.log(`Checksum: ${result.checksum} | Ops: ${result.ops}`);
+console.log(`Tempo Interno (WASM): ${(result.elapsed_ms / 1000).toFixed(4)}s`);
+console.log(`Tempo Total (JS + WASM): ${((end - start) / 1000).toFixed(4)}s`);
+console.log(`Overhead JS: ${(end - start - result.elapsed_ms).toFixed(2)}ms`);
+
+// Outros kernels pelo harness: node runner.js <kernel> [size]
+const [kernel, size] = process.argv.slice(2);
+if (kernel) {
+    /** @type {import('./pkg/wasm_frontend').BenchOptions} */
+    const options = { kernel, size: size ? parseInt(size) : undefined };
+    const r = run_bench(options);
+    console.log(`\n${r.kernel} (size ${r.size}): mediana ${r.elapsed_ms.toFixed(2)}ms de ${r.stats?.reps} | ${r.gflops.toFixed(2)} G-ops/s`);
+}
@@ -1,9 +1,151 @@
+use serde::{Deserialize, Serialize};
+use tsify::Tsify;
 use wasm_bindgen::prelude::*;
-use core_algo::heavy_computation;
+use wasm_bindgen::JsCast;
+use core_algo::{bench_kernel, catalog, heavy_computation, kernels, simd_backend, verify, BenchConfig, BenchResult, Mode, Precision, Verification};
 
+// Conta as alocações do wasm (o `memory` de cada resultado)
+#[global_allocator]
+static ALLOC: core_algo::CountingAlloc = core_algo::CountingAlloc;
+
+// Os tipos do .d.ts que o wasm-bindgen gera (pkg/wasm_frontend.d.ts) saem das próprias
+// structs: o tsify deriva BenchResult, Stats, KernelInfo, Verification... no core-algo
+// (feature `ts`) e BenchOptions/WasmBenchResult aqui. Estes só dão nome às listas
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "BenchResult[]")]
+    pub type JsBenchResults;
+    #[wasm_bindgen(typescript_type = "KernelInfo[]")]
+    pub type JsKernelInfos;
+}
+
+type JsBenchOptions = <BenchOptions as Tsify>::JsType;
+type JsWasmBenchResult = <WasmBenchResult as Tsify>::JsType;
+type JsVerification = <Verification as Tsify>::JsType;
+
+// O que o `run_bench` aceita; campo ausente (ou undefined) fica com o padrão do harness/kernel
+#[derive(Deserialize, Tsify, Debug)]
+pub struct BenchOptions {
+    pub kernel: String,
+    // Padrão: o default_size do kernel
+    #[tsify(optional)]
+    pub size: Option<usize>,
+    // Só vale em build com threads
+    #[serde(default)]
+    pub parallel: bool,
+    #[tsify(optional)]
+    pub warmup: Option<usize>,
+    #[tsify(optional)]
+    pub reps: Option<usize>,
+    #[tsify(optional)]
+    pub seed: Option<u64>,
+    #[tsify(optional)]
+    pub precision: Option<Precision>,
+    #[tsify(optional)]
+    pub cutoff: Option<usize>,
+}
+
+// O BenchResult com o texto antigo junto (`formatted`)
+#[derive(Serialize, Tsify)]
+pub struct WasmBenchResult {
+    #[serde(flatten)]
+    pub result: BenchResult,
+    pub formatted: String,
+}
+
+// Devolve um objeto JS com os mesmos campos do JSON do /api/native
+// (o texto antigo continua disponível via `formatted`)
 #[wasm_bindgen]
-pub fn run_wasm_bench(size: usize) -> String {
+pub fn run_wasm_bench(size: usize) -> Result<JsWasmBenchResult, JsValue> {
     // Console log opcional
     web_sys::console::log_1(&"Iniciando WASM...".into());
-    heavy_computation(size)
-}
\ No newline at end of file
+    to_js(&heavy_computation(size))
+}
+
+// Com a feature `threads`, o JS chama `await initThreadPool(navigator.hardwareConcurrency)`
+// antes de rodar em paralelo (de dentro de um Worker: a thread principal do browser não pode bloquear)
+#[cfg(all(target_arch = "wasm32", feature = "threads"))]
+pub use wasm_bindgen_rayon::init_thread_pool;
+
+// Se este build foi compilado com threads (rayon de verdade no wasm)
+#[wasm_bindgen]
+pub fn threads_enabled() -> bool {
+    cfg!(feature = "threads")
+}
+
+// Qualquer kernel do registro pelo nome (ver list_kernels), com aquecimento e repetições:
+//   run_bench({ kernel: "matmul-strassen", size: 512, reps: 3, precision: "f32", cutoff: 32 })
+// `precision` e `cutoff` só nos kernels que têm essa escolha (ver list_kernels)
+#[wasm_bindgen]
+pub fn run_bench(options: JsBenchOptions) -> Result<JsWasmBenchResult, JsValue> {
+    let options: BenchOptions = serde_wasm_bindgen::from_value(options.into())?;
+    let mode = match options.parallel {
+        true if threads_enabled() => Mode::Parallel,
+        true => return Err("este build do wasm não tem threads (feature `threads`)".into()),
+        false => Mode::Single,
+    };
+    let config = BenchConfig {
+        precision: options.precision,
+        cutoff: options.cutoff,
+        ..BenchConfig::new(options.warmup, options.reps, options.seed).map_err(|e| JsValue::from_str(&e))?
+    };
+    let kernel = kernels::resolve(&options.kernel, &config).map_err(|e| JsValue::from_str(&e))?;
+    let size = options.size.unwrap_or_else(|| kernel.default_size());
+    web_sys::console::log_1(&format!("Iniciando WASM {} ({:?})...", options.kernel, mode).into());
+    to_js(&bench_kernel(&options.kernel, size, mode, config).expect("kernel resolvido"))
+}
+
+// [{ name, description, default_size, max_size, precisions, cutoff }, ...]
+#[wasm_bindgen]
+pub fn list_kernels() -> Result<JsKernelInfos, JsValue> {
+    Ok(to_value(&catalog())?.unchecked_into())
+}
+
+// Confere uma lista de resultados (browser, /api/native, /api/wasm, gpu-bench) do mesmo
+// kernel/size/seed; devolve { ok, reference, checks: [{ target, precision, rel_error, tolerance, ok }] }
+#[wasm_bindgen]
+pub fn verify_results(results: JsBenchResults) -> Result<JsVerification, JsValue> {
+    let results: Vec<BenchResult> = serde_wasm_bindgen::from_value(results.into())?;
+    let verification = verify(&results).map_err(|e| JsValue::from_str(&e))?;
+    Ok(to_value(&verification)?.unchecked_into())
+}
+
+// Caminho SIMD compilado no módulo ("simd128" ou "scalar"), usado pelo matmul-simd
+#[wasm_bindgen(js_name = simd_backend)]
+pub fn wasm_simd_backend() -> String {
+    simd_backend().to_string()
+}
+
+// Option sem skip vira null, como no JSON da API (e no `T | null` do .d.ts); o
+// `flatten` do WasmBenchResult passa por um mapa, que tem que sair como objeto comum
+fn to_value<T: Serialize>(value: &T) -> Result<JsValue, serde_wasm_bindgen::Error> {
+    value.serialize(
+        &serde_wasm_bindgen::Serializer::new()
+            .serialize_missing_as_null(true)
+            .serialize_maps_as_objects(true),
+    )
+}
+
+fn to_js(result: &BenchResult) -> Result<JsWasmBenchResult, JsValue> {
+    let result = WasmBenchResult {
+        formatted: result.to_string(),
+        result: result.clone(),
+    };
+    Ok(to_value(&result)?.unchecked_into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // As declarações que o tsify gera a partir das structs
+    #[test]
+    fn typescript_comes_from_rust_types() {
+        assert!(BenchResult::DECL.contains("precision?: Precision;"), "{}", BenchResult::DECL);
+        assert!(BenchResult::DECL.contains("memory?: MemoryStats;"));
+        assert!(BenchOptions::DECL.contains("kernel: string;"));
+        assert!(BenchOptions::DECL.contains("seed?: number;"));
+        assert!(BenchOptions::DECL.contains("parallel?: boolean;"));
+        assert_eq!(WasmBenchResult::DECL, "export interface WasmBenchResult extends BenchResult {\n    formatted: string;\n}");
+    }
+}
@@ -0,0 +1,197 @@
+// Os motores comparados. Cada um compila (ou prepara) o módulo uma vez e depois roda
+// `core-algo-wasi <kernel> <size> <warmup> <reps> <seed> [precision]` numa instância nova por kernel.
+// O tempo de cada execução é medido dentro do guest (mesmo harness do nativo),
+// então a compilação fica de fora e aparece separada em `compile_ms`.
+
+use core_algo::{bench_kernel, kernels, BenchConfig, BenchResult, Mode, Precision, Stopwatch};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Engine {
+    // core-algo direto, uma thread (o wasm também roda numa só): a referência
+    Native,
+    // wasmtime com Cranelift (otimizador completo, o mesmo do /api/wasm)
+    Cranelift,
+    // wasmtime com Winch (compilação rápida, código pior)
+    Winch,
+    // Interpretador wasmi
+    Wasmi,
+}
+
+pub const ALL: [Engine; 4] = [Engine::Native, Engine::Cranelift, Engine::Winch, Engine::Wasmi];
+
+impl Engine {
+    pub fn name(self) -> &'static str {
+        match self {
+            Engine::Native => "native",
+            Engine::Cranelift => "cranelift",
+            Engine::Winch => "winch",
+            Engine::Wasmi => "wasmi",
+        }
+    }
+}
+
+impl FromStr for Engine {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ALL.into_iter()
+            .find(|e| e.name() == s)
+            .ok_or_else(|| format!("motor desconhecido: {} (native, cranelift, winch ou wasmi)", s))
+    }
+}
+
+// O guest só escreve uma linha de JSON (ou uma mensagem de erro)
+const MAX_OUTPUT: usize = 64 * 1024;
+
+fn guest_args(kernel: &str, size: usize, config: BenchConfig) -> Vec<String> {
+    let mut args = vec![
+        "core-algo-wasi".to_string(),
+        kernel.to_string(),
+        size.to_string(),
+        config.warmup.to_string(),
+        config.reps.to_string(),
+        config.seed.to_string(),
+    ];
+    // Posicionais: com cutoff a precisão vai junto (f64, o padrão das matmul, se ninguém escolheu)
+    match config.cutoff {
+        Some(cutoff) => args.extend([config.precision.unwrap_or(Precision::F64).to_string(), cutoff.to_string()]),
+        None => args.extend(config.precision.map(|p| p.to_string())),
+    }
+    args
+}
+
+// Código de saída + stdout + stderr do guest -> BenchResult
+fn guest_result(code: i32, stdout: &[u8], stderr: &[u8]) -> Result<BenchResult, String> {
+    if code != 0 {
+        return Err(format!(
+            "core-algo-wasi saiu com {}: {}",
+            code,
+            String::from_utf8_lossy(stderr).trim()
+        ));
+    }
+    serde_json::from_slice(stdout).map_err(|e| format!("saída do wasm: {}", e))
+}
+
+// Um motor pronto para rodar kernels
+pub enum Prepared {
+    Native,
+    Wasmtime {
+        engine: wasmtime::Engine,
+        pre: wasmtime::InstancePre<wasmtime_wasi::p1::WasiP1Ctx>,
+    },
+    Wasmi {
+        engine: wasmi::Engine,
+        module: wasmi::Module,
+    },
+}
+
+impl Prepared {
+    // Compila/valida o módulo; devolve também quanto tempo isso levou (ms)
+    pub fn new(engine: Engine, wasm: &[u8]) -> Result<(Self, f64), String> {
+        let start = Stopwatch::start();
+        let prepared = match engine {
+            Engine::Native => Prepared::Native,
+            Engine::Cranelift | Engine::Winch => {
+                let strategy = if engine == Engine::Winch {
+                    wasmtime::Strategy::Winch
+                } else {
+                    wasmtime::Strategy::Cranelift
+                };
+                let mut config = wasmtime::Config::new();
+                config.strategy(strategy);
+                let engine = wasmtime::Engine::new(&config).map_err(|e| e.to_string())?;
+                let module = wasmtime::Module::new(&engine, wasm).map_err(|e| format!("compilação: {}", e))?;
+                let mut linker = wasmtime::Linker::new(&engine);
+                wasmtime_wasi::p1::add_to_linker_sync(&mut linker, |ctx| ctx).map_err(|e| e.to_string())?;
+                let pre = linker.instantiate_pre(&module).map_err(|e| format!("instanciação: {}", e))?;
+                Prepared::Wasmtime { engine, pre }
+            }
+            Engine::Wasmi => {
+                let engine = wasmi::Engine::default();
+                let module = wasmi::Module::new(&engine, wasm).map_err(|e| format!("validação: {}", e))?;
+                Prepared::Wasmi { engine, module }
+            }
+        };
+        Ok((prepared, start.elapsed_ms()))
+    }
+
+    pub fn run(&self, kernel: &str, size: usize, config: BenchConfig) -> Result<BenchResult, String> {
+        match self {
+            Prepared::Native => {
+                kernels::resolve(kernel, &config)?;
+                Ok(bench_kernel(kernel, size, Mode::Single, config).expect("kernel resolvido"))
+            }
+            Prepared::Wasmtime { engine, pre } => run_wasmtime(engine, pre, &guest_args(kernel, size, config)),
+            Prepared::Wasmi { engine, module } => run_wasmi(engine, module, &guest_args(kernel, size, config)),
+        }
+    }
+}
+
+fn run_wasmtime(
+    engine: &wasmtime::Engine,
+    pre: &wasmtime::InstancePre<wasmtime_wasi::p1::WasiP1Ctx>,
+    args: &[String],
+) -> Result<BenchResult, String> {
+    use wasmtime_wasi::p2::pipe::MemoryOutputPipe;
+
+    let stdout = MemoryOutputPipe::new(MAX_OUTPUT);
+    let stderr = MemoryOutputPipe::new(MAX_OUTPUT);
+    let wasi = wasmtime_wasi::WasiCtxBuilder::new()
+        .args(args)
+        .stdout(stdout.clone())
+        .stderr(stderr.clone())
+        .build_p1();
+
+    let mut store = wasmtime::Store::new(engine, wasi);
+    let instance = pre.instantiate(&mut store).map_err(|e| format!("instância: {}", e))?;
+    let start = instance
+        .get_typed_func::<(), ()>(&mut store, "_start")
+        .map_err(|e| format!("_start: {}", e))?;
+
+    // Um comando WASI que termina com exit(n) chega aqui como "erro"
+    let code = match start.call(&mut store, ()) {
+        Ok(()) => 0,
+        Err(e) => match e.downcast_ref::<wasmtime_wasi::I32Exit>() {
+            Some(exit) => exit.0,
+            None => return Err(format!("execução: {}", e)),
+        },
+    };
+    drop(store);
+    guest_result(code, &stdout.contents(), &stderr.contents())
+}
+
+fn run_wasmi(engine: &wasmi::Engine, module: &wasmi::Module, args: &[String]) -> Result<BenchResult, String> {
+    use wasmi_wasi::wasi_common::pipe::WritePipe;
+
+    let stdout = WritePipe::new_in_memory();
+    let stderr = WritePipe::new_in_memory();
+    let wasi = wasmi_wasi::WasiCtxBuilder::new()
+        .args(args)
+        .map_err(|e| e.to_string())?
+        .stdout(Box::new(stdout.clone()))
+        .stderr(Box::new(stderr.clone()))
+        .build();
+
+    let mut store = wasmi::Store::new(engine, wasi);
+    let mut linker = wasmi::Linker::new(engine);
+    wasmi_wasi::add_to_linker(&mut linker, |ctx| ctx).map_err(|e| e.to_string())?;
+    let instance = linker
+        .instantiate_and_start(&mut store, module)
+        .map_err(|e| format!("instância: {}", e))?;
+    let start = instance
+        .get_typed_func::<(), ()>(&store, "_start")
+        .map_err(|e| format!("_start: {}", e))?;
+
+    let code = match start.call(&mut store, ()) {
+        Ok(()) => 0,
+        Err(e) => match e.i32_exit_status() {
+            Some(code) => code,
+            None => return Err(format!("execução: {}", e)),
+        },
+    };
+    // O store segura a outra ponta dos pipes
+    drop(store);
+    let contents = |pipe: WritePipe<std::io::Cursor<Vec<u8>>>| pipe.try_into_inner().map(|c| c.into_inner()).unwrap_or_default();
+    guest_result(code, &contents(stdout), &contents(stderr))
+}
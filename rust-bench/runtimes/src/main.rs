@@ -0,0 +1,246 @@
+// --- RUNTIME-BENCH ---
+// O mesmo kernel no nativo e no build WASI (core-algo-wasi) dentro de vários motores:
+// wasmtime com Cranelift, wasmtime com Winch e o interpretador wasmi. Tudo numa thread,
+// no mesmo processo, sem browser nem rede.
+//   runtime-bench [opções] <kernel[:size]>...
+use core_algo::{kernels, verify, BenchConfig, BenchResult};
+use engines::{Engine, Prepared};
+use serde::Serialize;
+use std::process::ExitCode;
+
+mod engines;
+
+// O nativo conta as alocações como o guest (que instala o mesmo alocador)
+#[global_allocator]
+static ALLOC: core_algo::CountingAlloc = core_algo::CountingAlloc;
+
+const MODULE_ENV: &str = "WASM_MODULE";
+const DEFAULT_MODULE: &str = "target/wasm32-wasip1/release/core-algo-wasi.wasm";
+
+const USAGE: &str = "\
+uso: runtime-bench [opções] <kernel[:size]>...
+
+  kernel       nome do kernel; `:size` troca o size (padrão: o default do kernel)
+  --engines L  motores, ex. native,wasmi (padrão: native,cranelift,winch,wasmi)
+  --module P   o .wasm do core-algo-wasi (padrão: $WASM_MODULE ou o build release)
+  --warmup N   execuções sem medir antes das repetições (padrão 1)
+  --reps N     repetições medidas (padrão 5)
+  --seed N     seed das entradas (padrão 42)
+  --precision P  f32 | f64 | i32 | i64 nas matmul (padrão f64)
+  --cutoff N   lado em que matmul-recursive/matmul-strassen param de dividir (padrão 64)
+  --json       lista de resultados em JSON em vez da tabela
+
+O módulo sai de: cargo build -p core-algo --bin core-algo-wasi --release --target wasm32-wasip1";
+
+struct Args {
+    runs: Vec<(&'static str, usize)>,
+    engines: Vec<Engine>,
+    module: String,
+    config: BenchConfig,
+    json: bool,
+}
+
+fn number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("{}: número inválido '{}'", flag, value))
+}
+
+// None = --help
+fn parse(args: &[String]) -> Result<Option<Args>, String> {
+    let mut runs = Vec::new();
+    let mut engines = engines::ALL.to_vec();
+    let mut module = std::env::var(MODULE_ENV).unwrap_or_else(|_| DEFAULT_MODULE.to_string());
+    let (mut warmup, mut reps, mut seed, mut precision, mut cutoff) = (None, None, None, None, None);
+    let mut json = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or_else(|| format!("{} precisa de um valor", arg));
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--json" => json = true,
+            "--module" => module = value()?.clone(),
+            "--warmup" => warmup = Some(number(arg, value()?)?),
+            "--reps" => reps = Some(number(arg, value()?)?),
+            "--seed" => seed = Some(number(arg, value()?)?),
+            "--precision" => precision = Some(value()?.parse()?),
+            "--cutoff" => cutoff = Some(number(arg, value()?)?),
+            "--engines" => engines = value()?.split(',').map(|e| e.trim().parse()).collect::<Result<_, _>>()?,
+            flag if flag.starts_with('-') => return Err(format!("opção desconhecida: {}", flag)),
+            name => {
+                let (name, size) = match name.split_once(':') {
+                    Some((name, size)) => (name, Some(number::<usize>(name, size)?)),
+                    None => (name, None),
+                };
+                let kernel = kernels::find(name).ok_or_else(|| format!("kernel desconhecido: {}", name))?;
+                runs.push((kernel.name(), size.unwrap_or_else(|| kernel.default_size())));
+            }
+        }
+    }
+    if runs.is_empty() {
+        return Err("nenhum kernel".to_string());
+    }
+    let config = BenchConfig {
+        precision,
+        cutoff,
+        ..BenchConfig::new(warmup, reps, seed)?
+    };
+    // Depois do laço: --precision e --cutoff podem vir depois dos kernels
+    for (name, _) in &runs {
+        kernels::resolve(name, &config)?;
+    }
+    Ok(Some(Args {
+        runs,
+        engines,
+        module,
+        config,
+        json,
+    }))
+}
+
+#[derive(Serialize)]
+struct Run {
+    engine: &'static str,
+    kernel: &'static str,
+    size: usize,
+    // Compilação/validação do módulo inteiro (uma vez por motor)
+    compile_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<BenchResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+fn print_table(runs: &[Run]) {
+    println!(
+        "{:<18} {:>9} {:<10} {:>12} {:>12} {:>9} {:>9}  checksum",
+        "kernel", "size", "motor", "compilação", "mediana ms", "x nativo", "pico MiB"
+    );
+    for run in runs {
+        let native = runs
+            .iter()
+            .find(|r| r.engine == "native" && r.kernel == run.kernel && r.size == run.size)
+            .and_then(|r| r.result.as_ref());
+        // Nativo: o CountingAlloc deste processo; wasm: o do guest
+        let peak = run
+            .result
+            .as_ref()
+            .and_then(|r| r.memory.as_ref()?.peak_bytes)
+            .map_or("-".to_string(), |peak| format!("{:.1}", peak as f64 / (1024.0 * 1024.0)));
+        let (median, ratio, checksum) = match (&run.result, &run.error) {
+            (Some(r), _) => {
+                let ratio = native.map_or("-".to_string(), |n| format!("{:.2}x", r.elapsed_ms / n.elapsed_ms));
+                // Mesma tolerância do modo de verificação
+                let same = native.map(|n| verify(&[n.clone(), r.clone()]).is_ok_and(|v| v.ok));
+                let checksum = match same {
+                    Some(true) => format!("{} (igual)", r.checksum),
+                    Some(false) => format!("{} (DIFERENTE)", r.checksum),
+                    None => r.checksum.to_string(),
+                };
+                (format!("{:.2}", r.elapsed_ms), ratio, checksum)
+            }
+            (None, error) => ("-".to_string(), "-".to_string(), format!("erro: {}", error.as_deref().unwrap_or("?"))),
+        };
+        println!(
+            "{:<18} {:>9} {:<10} {:>12} {:>12} {:>9} {:>9}  {}",
+            run.kernel,
+            run.size,
+            run.engine,
+            format!("{:.1}", run.compile_ms),
+            median,
+            ratio,
+            peak,
+            checksum
+        );
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args = match parse(&args) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let wasm = if args.engines.iter().any(|&e| e != Engine::Native) {
+        match std::fs::read(&args.module) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                eprintln!("módulo {}: {}\n\n{}", args.module, e, USAGE);
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        Vec::new()
+    };
+
+    let mut runs = Vec::new();
+    for &engine in &args.engines {
+        eprintln!("{}: preparando o módulo...", engine.name());
+        let prepared = Prepared::new(engine, &wasm);
+        for &(kernel, size) in &args.runs {
+            let (result, compile_ms) = match &prepared {
+                Ok((prepared, compile_ms)) => {
+                    eprintln!("{}: {} (size {})...", engine.name(), kernel, size);
+                    (prepared.run(kernel, size, args.config), *compile_ms)
+                }
+                Err(e) => (Err(e.clone()), 0.0),
+            };
+            runs.push(Run {
+                engine: engine.name(),
+                kernel,
+                size,
+                compile_ms,
+                error: result.as_ref().err().cloned(),
+                result: result.ok(),
+            });
+        }
+    }
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&runs).expect("Run serializável"));
+    } else {
+        print_table(&runs);
+    }
+    if runs.iter().any(|r| r.error.is_some()) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Result<Args, String> {
+        let words: Vec<String> = line.split_whitespace().map(String::from).collect();
+        parse(&words)?.ok_or_else(|| "não era uma execução".to_string())
+    }
+
+    #[test]
+    fn parses_kernels_and_engines() {
+        let parsed = args("sieve:1000 matmul-tiled --engines native,wasmi --reps 2 --module m.wasm --json").unwrap();
+        assert_eq!(parsed.runs, [("sieve", 1000), ("matmul-tiled", 400)]);
+        assert_eq!(parsed.engines, [Engine::Native, Engine::Wasmi]);
+        assert_eq!(parsed.config.reps, 2);
+        assert_eq!(parsed.module, "m.wasm");
+        assert!(parsed.json);
+        assert_eq!(args("sieve").unwrap().engines, engines::ALL);
+
+        assert!(args("").is_err());
+        assert!(args("nao-existe").is_err());
+        assert!(args("sieve --engines v8").is_err());
+        assert!(args("sieve --module").is_err());
+        assert!(args("matmul --precision i32").is_ok());
+        assert!(args("sieve --precision i32").is_err());
+        assert_eq!(args("matmul-strassen --cutoff 32").unwrap().config.cutoff, Some(32));
+        assert!(args("matmul --cutoff 32").is_err());
+    }
+}
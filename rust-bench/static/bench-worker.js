@@ -0,0 +1,18 @@
+// Worker que roda os kernels com threads (build com a feature `threads`).
+// O rayon bloqueia esperando as outras threads, e a thread principal do browser não pode bloquear.
+import init, { initThreadPool, run_bench } from './pkg/wasm_frontend.js';
+
+const ready = (async () => {
+    await init();
+    await initThreadPool(navigator.hardwareConcurrency);
+})();
+
+// `data` é um BenchOptions (ver pkg/wasm_frontend.d.ts) sem o `parallel`
+onmessage = async ({ data }) => {
+    try {
+        await ready;
+        postMessage({ ok: true, result: run_bench({ ...data, parallel: true }) });
+    } catch (e) {
+        postMessage({ ok: false, error: String(e) });
+    }
+};
@@ -0,0 +1,154 @@
+// --- TLS OPCIONAL (rustls) ---
+// Se TLS_CERT e TLS_KEY apontarem para arquivos PEM, o servidor sobe em https (e wss, no
+// sync-demo). Sem as variáveis, continua em http puro. Usado pelo backend e pelo sync-demo.
+
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::ServerConfig;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+pub const CERT_ENV: &str = "TLS_CERT";
+pub const KEY_ENV: &str = "TLS_KEY";
+
+fn invalid(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
+
+// Monta a configuração do rustls a partir do certificado (cadeia) e da chave privada
+pub fn load_rustls_config(cert_path: &Path, key_path: &Path) -> io::Result<ServerConfig> {
+    let certs = CertificateDer::pem_file_iter(cert_path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| invalid(format!("certificado {}: {}", cert_path.display(), e)))?;
+    if certs.is_empty() {
+        return Err(invalid(format!(
+            "nenhum certificado em {}",
+            cert_path.display()
+        )));
+    }
+
+    let key = PrivateKeyDer::from_pem_file(key_path)
+        .map_err(|e| invalid(format!("chave {}: {}", key_path.display(), e)))?;
+
+    ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .and_then(|builder| builder.with_no_client_auth().with_single_cert(certs, key))
+        .map_err(|e| invalid(format!("configuração TLS: {}", e)))
+}
+
+// Lê TLS_CERT/TLS_KEY: as duas ou nenhuma
+pub fn from_env() -> io::Result<Option<ServerConfig>> {
+    match (std::env::var_os(CERT_ENV), std::env::var_os(KEY_ENV)) {
+        (Some(cert), Some(key)) => load_rustls_config(Path::new(&cert), Path::new(&key)).map(Some),
+        (None, None) => Ok(None),
+        _ => Err(invalid(format!("defina {} e {} juntos", CERT_ENV, KEY_ENV))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{web, App, HttpResponse, HttpServer};
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::path::PathBuf;
+
+    // Certificado autoassinado para "localhost", gerado na hora num diretório temporário só
+    // deste teste; o diretório some quando o valor sai de escopo
+    struct SelfSigned {
+        dir: PathBuf,
+        cert: PathBuf,
+        key: PathBuf,
+        der: CertificateDer<'static>,
+    }
+
+    impl SelfSigned {
+        fn new(test: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("tls-config-{}-{}", test, std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+
+            let generated = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+            let (cert, key) = (dir.join("cert.pem"), dir.join("key.pem"));
+            std::fs::write(&cert, generated.cert.pem()).unwrap();
+            std::fs::write(&key, generated.key_pair.serialize_pem()).unwrap();
+            SelfSigned {
+                der: generated.cert.der().clone(),
+                dir,
+                cert,
+                key,
+            }
+        }
+    }
+
+    impl Drop for SelfSigned {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    #[test]
+    fn loads_self_signed_pair() {
+        let pair = SelfSigned::new("load");
+        assert!(load_rustls_config(&pair.cert, &pair.key).is_ok());
+
+        // Trocar os arquivos de lugar tem que dar erro, não pânico
+        assert!(load_rustls_config(&pair.key, &pair.cert).is_err());
+        assert!(load_rustls_config(Path::new("/nao/existe.pem"), &pair.key).is_err());
+
+        let dir = pair.dir.clone();
+        drop(pair);
+        assert!(!dir.exists());
+    }
+
+    #[actix_web::test]
+    async fn serves_https_with_self_signed_cert() {
+        let pair = SelfSigned::new("serve");
+        let config = load_rustls_config(&pair.cert, &pair.key).unwrap();
+        let der = pair.der.clone();
+
+        let server = HttpServer::new(|| {
+            App::new().route(
+                "/",
+                web::get().to(|| async { HttpResponse::Ok().body("ok") }),
+            )
+        })
+        .workers(1)
+        .bind_rustls_0_23(("127.0.0.1", 0), config)
+        .unwrap();
+        let addr = server.addrs()[0];
+        let server = server.run();
+        let handle = server.handle();
+        actix_web::rt::spawn(server);
+
+        // Cliente rustls que só confia no certificado gerado acima
+        let response = web::block(move || {
+            let mut roots = rustls::RootCertStore::empty();
+            roots.add(der).unwrap();
+            let client = rustls::ClientConfig::builder_with_provider(Arc::new(
+                rustls::crypto::ring::default_provider(),
+            ))
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+            let conn =
+                rustls::ClientConnection::new(Arc::new(client), "localhost".try_into().unwrap())
+                    .unwrap();
+            let mut tls = rustls::StreamOwned::new(conn, TcpStream::connect(addr).unwrap());
+
+            tls.write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+                .unwrap();
+            let mut response = String::new();
+            // O servidor pode fechar sem close_notify; o que importa é o que chegou
+            let _ = tls.read_to_string(&mut response);
+            response
+        })
+        .await
+        .unwrap();
+
+        handle.stop(false).await;
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.ends_with("ok"));
+    }
+}
@@ -0,0 +1,243 @@
+// --- CANAIS DE EVENTOS ---
+// Além da simulação, os clientes trocam eventos por canais com nome.
+// Cada canal tem sua regra de entrega:
+//   chat      -> confiável e em ordem, com histórico limitado que quem entra recebe
+//   cursor    -> só o último valor de cada usuário (os intermediários são descartados no tick)
+//   custom:*  -> repassado na hora para todo mundo, sem guardar nada por canal (o seq vem de
+//                um contador único dos custom:*, então cresce em cada canal mas com buracos)
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use uuid::Uuid;
+
+// Quantas mensagens de chat quem acabou de entrar recebe
+pub const CHAT_BACKLOG: usize = 50;
+
+// Tamanho máximo do "data" de um evento (JSON serializado)
+const MAX_EVENT_BYTES: usize = 2048;
+
+// Tamanho máximo do nome de um canal custom:* (o nome vai em todo evento repassado)
+const MAX_CHANNEL_NAME: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Delivery {
+    // Guarda as últimas N mensagens, em ordem
+    History(usize),
+    // Só o valor mais recente de cada remetente
+    Latest,
+    // Repassa e esquece
+    Relay,
+}
+
+impl Delivery {
+    pub fn for_channel(name: &str) -> Option<Delivery> {
+        match name {
+            "chat" => Some(Delivery::History(CHAT_BACKLOG)),
+            "cursor" => Some(Delivery::Latest),
+            _ if name.starts_with("custom:") && ("custom:".len() + 1..=MAX_CHANNEL_NAME).contains(&name.len()) => {
+                Some(Delivery::Relay)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ChannelError {
+    UnknownChannel(String),
+    TooLarge(usize),
+}
+
+impl std::fmt::Display for ChannelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChannelError::UnknownChannel(name) => write!(f, "canal desconhecido: {}", name),
+            ChannelError::TooLarge(size) => {
+                write!(f, "evento grande demais: {} bytes (máx {})", size, MAX_EVENT_BYTES)
+            }
+        }
+    }
+}
+
+// O que vai para os clientes: { "id": remetente, "channel": ..., "seq": ..., "data": ... }
+#[derive(Serialize, Debug, Clone)]
+pub struct ChannelEvent {
+    pub id: Uuid,
+    pub channel: String,
+    // Número de sequência por canal: o cliente usa para ordenar e ignorar repetidos
+    pub seq: u64,
+    pub data: Value,
+}
+
+impl ChannelEvent {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("evento serializável")
+    }
+}
+
+#[derive(Default)]
+pub struct Channels {
+    // Só dos canais fixos (chat, cursor); os custom:* dividem `relay_seq`
+    seq: HashMap<String, u64>,
+    relay_seq: u64,
+    history: HashMap<String, VecDeque<ChannelEvent>>,
+    // Último valor de cada remetente nos canais "latest-only"
+    latest: HashMap<String, HashMap<Uuid, ChannelEvent>>,
+    // Valores novos desde o último tick, ainda não enviados
+    pending: HashMap<(String, Uuid), ChannelEvent>,
+}
+
+impl Channels {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Publica um evento. Retorna o JSON que deve ir para a sala agora
+    // (None quando o canal só entrega no próximo tick)
+    pub fn publish(&mut self, id: Uuid, channel: &str, data: Value) -> Result<Option<String>, ChannelError> {
+        let delivery = Delivery::for_channel(channel)
+            .ok_or_else(|| ChannelError::UnknownChannel(channel.to_string()))?;
+
+        let size = data.to_string().len();
+        if size > MAX_EVENT_BYTES {
+            return Err(ChannelError::TooLarge(size));
+        }
+
+        // Um custom:* novo por evento não pode deixar uma entrada para sempre no mapa
+        let seq = match delivery {
+            Delivery::Relay => &mut self.relay_seq,
+            _ => self.seq.entry(channel.to_string()).or_insert(0),
+        };
+        *seq += 1;
+        let event = ChannelEvent {
+            id,
+            channel: channel.to_string(),
+            seq: *seq,
+            data,
+        };
+
+        match delivery {
+            Delivery::History(capacity) => {
+                let json = event.to_json();
+                let history = self.history.entry(channel.to_string()).or_default();
+                if history.len() == capacity {
+                    history.pop_front();
+                }
+                history.push_back(event);
+                Ok(Some(json))
+            }
+            Delivery::Latest => {
+                self.latest
+                    .entry(channel.to_string())
+                    .or_default()
+                    .insert(id, event.clone());
+                self.pending.insert((channel.to_string(), id), event);
+                Ok(None)
+            }
+            Delivery::Relay => Ok(Some(event.to_json())),
+        }
+    }
+
+    // Chamado a cada tick: só o último valor de cada remetente sai
+    pub fn flush(&mut self) -> Vec<String> {
+        let mut events: Vec<ChannelEvent> = self.pending.drain().map(|(_, e)| e).collect();
+        events.sort_by(|a, b| a.channel.cmp(&b.channel).then(a.seq.cmp(&b.seq)));
+        events.iter().map(ChannelEvent::to_json).collect()
+    }
+
+    // O que quem acabou de entrar precisa receber: histórico + últimos valores
+    pub fn backlog(&self) -> Vec<String> {
+        let mut events: Vec<&ChannelEvent> = self
+            .history
+            .values()
+            .flatten()
+            .chain(self.latest.values().flat_map(|m| m.values()))
+            .collect();
+        events.sort_by(|a, b| a.channel.cmp(&b.channel).then(a.seq.cmp(&b.seq)));
+        events.into_iter().map(ChannelEvent::to_json).collect()
+    }
+
+    // Quem saiu não deixa cursor "fantasma" para os próximos que entrarem
+    pub fn forget(&mut self, id: Uuid) {
+        for values in self.latest.values_mut() {
+            values.remove(&id);
+        }
+        self.pending.retain(|(_, sender), _| *sender != id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn chat_backlog_is_ordered_and_bounded() {
+        let mut channels = Channels::new();
+        let id = Uuid::new_v4();
+        for i in 0..CHAT_BACKLOG + 10 {
+            assert!(channels.publish(id, "chat", json!({ "text": i })).unwrap().is_some());
+        }
+
+        let backlog: Vec<Value> = channels
+            .backlog()
+            .iter()
+            .map(|s| serde_json::from_str(s).unwrap())
+            .collect();
+        assert_eq!(backlog.len(), CHAT_BACKLOG);
+        assert_eq!(backlog[0]["data"]["text"], 10);
+        assert!(backlog.windows(2).all(|w| w[0]["seq"].as_u64() < w[1]["seq"].as_u64()));
+    }
+
+    #[test]
+    fn cursor_keeps_only_latest_per_sender() {
+        let mut channels = Channels::new();
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        for x in 0..5 {
+            assert!(channels.publish(a, "cursor", json!({ "x": x })).unwrap().is_none());
+        }
+        channels.publish(b, "cursor", json!({ "x": 100 })).unwrap();
+
+        let flushed = channels.flush();
+        assert_eq!(flushed.len(), 2);
+        assert!(flushed[0].contains(r#""x":4"#));
+        assert!(channels.flush().is_empty());
+
+        channels.forget(a);
+        assert_eq!(channels.backlog().len(), 1);
+    }
+
+    #[test]
+    fn rejects_unknown_and_oversized_events() {
+        let mut channels = Channels::new();
+        let id = Uuid::new_v4();
+        assert!(channels.publish(id, "custom:ping", json!(1)).unwrap().is_some());
+        assert_eq!(
+            channels.publish(id, "admin", json!(1)),
+            Err(ChannelError::UnknownChannel("admin".into()))
+        );
+        assert!(matches!(
+            channels.publish(id, "chat", json!("x".repeat(MAX_EVENT_BYTES))),
+            Err(ChannelError::TooLarge(_))
+        ));
+        assert!(channels.backlog().is_empty());
+        let long = format!("custom:{}", "x".repeat(MAX_CHANNEL_NAME));
+        assert_eq!(channels.publish(id, &long, json!(1)), Err(ChannelError::UnknownChannel(long.clone())));
+    }
+
+    #[test]
+    fn custom_channels_keep_no_state() {
+        let mut channels = Channels::new();
+        let id = Uuid::new_v4();
+        let mut last = 0;
+        for _ in 0..1000 {
+            let json = channels.publish(id, &format!("custom:{}", Uuid::new_v4()), json!(1)).unwrap().unwrap();
+            let seq = serde_json::from_str::<Value>(&json).unwrap()["seq"].as_u64().unwrap();
+            assert!(seq > last);
+            last = seq;
+        }
+        assert!(channels.seq.is_empty() && channels.history.is_empty() && channels.latest.is_empty());
+        assert!(channels.backlog().is_empty() && channels.flush().is_empty());
+    }
+}
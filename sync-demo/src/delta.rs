@@ -0,0 +1,252 @@
+// --- SNAPSHOTS COM COMPRESSÃO DELTA ---
+// Em vez de mandar as posições completas em JSON a cada tick, o Lobby guarda
+// o último tick que cada cliente confirmou (ack) e manda só o que mudou desde ele.
+// Coordenadas vão quantizadas em 12 bits (0..1 -> 0..4095; o cliente divide por 4095),
+// que já é mais resolução que qualquer tela; o delta leva só o deslocamento
+// quantizado, que é um número pequeno (e zero para quem está quase parado).
+// Se o ack se perder por tempo demais (baseline saiu do histórico), ou se o delta
+// não ficar menor que o estado completo, manda tudo.
+
+use crate::physics::World;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+// Quantos ticks o servidor lembra (20 ticks/s -> ~1.6s de tolerância a acks perdidos)
+pub const HISTORY_LEN: usize = 32;
+
+const QUANT_MAX: f32 = 4095.0;
+
+pub fn quantize(v: f32) -> u16 {
+    (v.clamp(0.0, 1.0) * QUANT_MAX).round() as u16
+}
+
+// Estado "oficial" como o cliente o enxerga: já quantizado
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuantizedState {
+    pub tick: u64,
+    pub particles: Vec<[u16; 2]>,
+}
+
+impl QuantizedState {
+    pub fn from_world(world: &World) -> Self {
+        QuantizedState {
+            tick: world.tick,
+            particles: world
+                .particles
+                .iter()
+                .map(|p| [quantize(p.pos[0]), quantize(p.pos[1])])
+                .collect(),
+        }
+    }
+}
+
+// Partícula que mudou: [pulo, dx, dy].
+// `pulo` = quantas partículas paradas existem desde a última mudança (0 = a seguinte),
+// assim o índice quase sempre é um dígito só; dx/dy são o deslocamento quantizado.
+// Tupla para o JSON sair como array, sem repetir nome de campo
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Change(pub u32, pub i32, pub i32);
+
+// O que viaja na rede (vai dentro do campo "data" do envelope)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Snapshot {
+    // Estado completo (primeira mensagem ou ack perdido)
+    World { tick: u64, particles: Vec<[u16; 2]> },
+    // Diferença em relação ao tick `base`, que o cliente já confirmou
+    Delta { tick: u64, base: u64, changes: Vec<Change> },
+}
+
+impl Snapshot {
+    pub fn full(state: &QuantizedState) -> Self {
+        Snapshot::World {
+            tick: state.tick,
+            particles: state.particles.clone(),
+        }
+    }
+
+    pub fn delta(base: &QuantizedState, state: &QuantizedState) -> Self {
+        let mut changes = Vec::new();
+        let mut next = 0;
+
+        for (i, (new, old)) in state.particles.iter().zip(&base.particles).enumerate() {
+            if new != old {
+                changes.push(Change(
+                    (i - next) as u32,
+                    new[0] as i32 - old[0] as i32,
+                    new[1] as i32 - old[1] as i32,
+                ));
+                next = i + 1;
+            }
+        }
+
+        Snapshot::Delta {
+            tick: state.tick,
+            base: base.tick,
+            changes,
+        }
+    }
+
+    // Reconstrução do lado do cliente (o index.html faz o mesmo em JS).
+    // Retorna None se o baseline necessário não está disponível.
+    #[cfg(test)]
+    pub fn apply(&self, baseline: Option<&QuantizedState>) -> Option<QuantizedState> {
+        match self {
+            Snapshot::World { tick, particles } => Some(QuantizedState {
+                tick: *tick,
+                particles: particles.clone(),
+            }),
+            Snapshot::Delta { tick, base, changes } => {
+                let baseline = baseline.filter(|b| b.tick == *base)?;
+                let mut particles = baseline.particles.clone();
+                let mut next = 0;
+                for Change(skip, dx, dy) in changes {
+                    let i = next + *skip as usize;
+                    next = i + 1;
+                    let p = particles.get_mut(i)?;
+                    p[0] = u16::try_from(p[0] as i32 + dx).ok()?;
+                    p[1] = u16::try_from(p[1] as i32 + dy).ok()?;
+                }
+                Some(QuantizedState { tick: *tick, particles })
+            }
+        }
+    }
+}
+
+// Últimos estados enviados, para calcular deltas a partir do ack de cada cliente
+pub struct SnapshotHistory {
+    states: VecDeque<QuantizedState>,
+}
+
+impl SnapshotHistory {
+    pub fn new() -> Self {
+        SnapshotHistory {
+            states: VecDeque::with_capacity(HISTORY_LEN),
+        }
+    }
+
+    pub fn push(&mut self, state: QuantizedState) {
+        if self.states.len() == HISTORY_LEN {
+            self.states.pop_front();
+        }
+        self.states.push_back(state);
+    }
+
+    pub fn get(&self, tick: u64) -> Option<&QuantizedState> {
+        self.states.iter().find(|s| s.tick == tick)
+    }
+
+    pub fn latest(&self) -> Option<&QuantizedState> {
+        self.states.back()
+    }
+
+    // Delta a partir do baseline do cliente, ou completo se ele não existe mais.
+    // Já devolve o JSON, porque é o tamanho serializado que decide entre os dois
+    pub fn encode_for(&self, acked: Option<u64>) -> Option<String> {
+        let state = self.latest()?;
+        let full = serde_json::to_string(&Snapshot::full(state)).expect("snapshot serializável");
+
+        let base = acked
+            .and_then(|tick| self.get(tick))
+            .filter(|b| b.particles.len() == state.particles.len());
+        let Some(base) = base else {
+            return Some(full);
+        };
+
+        let delta = serde_json::to_string(&Snapshot::delta(base, state)).expect("snapshot serializável");
+        Some(if delta.len() < full.len() { delta } else { full })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Simula a rede: o cliente só vê o JSON
+    fn receive(json: &str) -> Snapshot {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn first_snapshot_is_full() {
+        let world = World::new(16);
+        let mut history = SnapshotHistory::new();
+        history.push(QuantizedState::from_world(&world));
+
+        let snapshot = receive(&history.encode_for(None).unwrap());
+        assert!(matches!(snapshot, Snapshot::World { .. }));
+
+        let rebuilt = snapshot.apply(None).unwrap();
+        assert_eq!(&rebuilt, history.latest().unwrap());
+    }
+
+    #[test]
+    fn acked_deltas_reconstruct_authoritative_state() {
+        let mut world = World::new(64);
+        let mut history = SnapshotHistory::new();
+        let mut acked: Option<u64> = None;
+        let mut client: Option<QuantizedState> = None;
+        let mut deltas = 0;
+
+        for step in 0..50 {
+            world.step();
+            if step == 20 {
+                world.perturb(0.5, 0.5);
+            }
+            history.push(QuantizedState::from_world(&world));
+
+            let snapshot = receive(&history.encode_for(acked).unwrap());
+            if matches!(snapshot, Snapshot::Delta { .. }) {
+                deltas += 1;
+            }
+
+            let rebuilt = snapshot.apply(client.as_ref()).unwrap();
+            assert_eq!(rebuilt, QuantizedState::from_world(&world));
+
+            acked = Some(rebuilt.tick);
+            client = Some(rebuilt);
+        }
+
+        assert!(deltas > 0, "nenhum delta foi enviado");
+    }
+
+    #[test]
+    fn delta_only_carries_changed_fields() {
+        let world = World::new(8);
+        let base = QuantizedState::from_world(&world);
+        let mut state = base.clone();
+        state.tick += 1;
+        state.particles[3][1] += 1;
+
+        match Snapshot::delta(&base, &state) {
+            Snapshot::Delta { changes, .. } => assert_eq!(changes, vec![Change(3, 0, 1)]),
+            other => panic!("esperava delta, veio {:?}", other),
+        }
+    }
+
+    #[test]
+    fn lost_acks_fall_back_to_full_snapshot() {
+        let mut world = World::new(16);
+        let mut history = SnapshotHistory::new();
+        history.push(QuantizedState::from_world(&world));
+        let acked = history.latest().unwrap().tick;
+
+        // Cliente some por mais ticks do que o histórico aguenta
+        for _ in 0..HISTORY_LEN {
+            world.step();
+            history.push(QuantizedState::from_world(&world));
+        }
+
+        let snapshot = receive(&history.encode_for(Some(acked)).unwrap());
+        assert!(matches!(snapshot, Snapshot::World { .. }));
+        assert_eq!(&snapshot.apply(None).unwrap(), history.latest().unwrap());
+    }
+
+    #[test]
+    fn delta_against_unknown_baseline_is_rejected() {
+        let world = World::new(4);
+        let state = QuantizedState::from_world(&world);
+        let delta = Snapshot::Delta { tick: 10, base: 9, changes: vec![] };
+        assert!(delta.apply(Some(&state)).is_none());
+    }
+}
@@ -1,11 +1,21 @@
-use actix::{Actor, StreamHandler, AsyncContext, Handler, Message, Context, Recipient, Addr};
+use actix::{Actor, ActorContext, StreamHandler, AsyncContext, Handler, Message, MessageResult, Context, Recipient, Addr};
 use actix_web::{web, App, Error, HttpRequest, HttpResponse, HttpServer};
 use actix_web_actors::ws;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::time::Duration;
 // Removi Arc e Mutex que não estavam sendo usados
 use uuid::Uuid;
 
+mod channels;
+mod delta;
+mod physics;
+mod rooms;
+use rooms::{Placement, Room, RoomPolicy};
+
+// De quanto em quanto tempo o servidor avança a simulação (20 ticks/s)
+const TICK_INTERVAL: Duration = Duration::from_millis(50);
+
 // --- ESTRUTURAS DE DADOS ---
 
 // A mensagem que viaja pela rede (JSON)
@@ -15,26 +25,111 @@ struct Position {
     y: f32,
 }
 
+// Eventos do Frontend que o servidor interpreta (o resto é só repassado)
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum ClientEvent {
+    // Clique na tela: empurra as partículas (coordenadas normalizadas 0..1)
+    Perturb(Position),
+    // Confirmação de que o cliente reconstruiu o estado deste tick
+    Ack { tick: u64 },
+    // Evento num canal com nome (chat, cursor, custom:*)
+    Event { channel: String, data: serde_json::Value },
+}
+
 // Mensagem interna do Rust para o Ator
 #[derive(Message)]
 #[rtype(result = "()")]
 struct WsMessage(String);
 
 // --- O HUB (LOBBY) ---
-// Ele guarda a lista de todo mundo que está online
+// Ele guarda a lista de todo mundo que está online, distribuído em salas
+// (cada uma com a simulação de partículas que o servidor roda)
 struct Lobby {
-    sessions: HashMap<Uuid, Recipient<WsMessage>>,
+    policy: RoomPolicy,
+    rooms: BTreeMap<usize, Room>,
+    // Em qual sala cada sessão está
+    members: HashMap<Uuid, usize>,
+    // Quem espera vaga (política "queue")
+    queue: VecDeque<(Uuid, Recipient<WsMessage>)>,
 }
 
 impl Lobby {
-    fn new() -> Self {
-        Lobby { sessions: HashMap::new() }
+    fn new(policy: RoomPolicy) -> Self {
+        let mut rooms = BTreeMap::new();
+        rooms.insert(1, Room::new(1));
+
+        Lobby {
+            policy,
+            rooms,
+            members: HashMap::new(),
+            queue: VecDeque::new(),
+        }
+    }
+
+    fn room_of(&mut self, id: Uuid) -> Option<&mut Room> {
+        let index = self.members.get(&id)?;
+        self.rooms.get_mut(index)
+    }
+
+    fn place(&self) -> Placement {
+        self.policy
+            .place(self.rooms.iter().map(|(index, room)| (*index, room.len())))
+    }
+
+    fn join(&mut self, index: usize, id: Uuid, addr: Recipient<WsMessage>) {
+        let room = self.rooms.entry(index).or_insert_with(|| Room::new(index));
+        addr.do_send(WsMessage(rooms::server_event(serde_json::json!({
+            "type": "room",
+            "room": room.name,
+            "capacity": self.policy.capacity,
+        }))));
+        room.join(id, addr);
+        self.members.insert(id, index);
+        println!("Novo usuário em {}! Total na sala: {}", room.name, room.len());
+    }
+
+    // Avisa cada um da fila da sua posição (1 = próximo a entrar)
+    fn notify_queue(&self) {
+        for (position, (_, addr)) in self.queue.iter().enumerate() {
+            addr.do_send(WsMessage(rooms::server_event(serde_json::json!({
+                "type": "queue",
+                "position": position + 1,
+                "waiting": self.queue.len(),
+            }))));
+        }
+    }
+
+    // Vagas abertas: puxa da fila enquanto couber
+    fn drain_queue(&mut self) {
+        let mut moved = false;
+        while !self.queue.is_empty() {
+            let index = match self.place() {
+                Placement::Join(index) | Placement::Create(index) => index,
+                Placement::Queue => break,
+            };
+            let (id, addr) = self.queue.pop_front().unwrap();
+            self.join(index, id, addr);
+            moved = true;
+        }
+        if moved {
+            self.notify_queue();
+        }
     }
 }
 
 // Transforma o Lobby em um Ator
 impl Actor for Lobby {
     type Context = Context<Self>;
+
+    // Liga o relógio da simulação (tick fixo, independente dos clientes)
+    fn started(&mut self, ctx: &mut Context<Self>) {
+        ctx.run_interval(TICK_INTERVAL, |lobby, _| {
+            for room in lobby.rooms.values_mut() {
+                room.tick();
+            }
+        });
+    }
 }
 
 // Mensagem para entrar no Lobby
@@ -49,8 +144,14 @@ impl Handler<Connect> for Lobby {
     type Result = ();
 
     fn handle(&mut self, msg: Connect, _: &mut Context<Self>) {
-        self.sessions.insert(msg.id, msg.addr);
-        println!("Novo usuário conectado! Total: {}", self.sessions.len());
+        match self.place() {
+            Placement::Join(index) | Placement::Create(index) => self.join(index, msg.id, msg.addr),
+            Placement::Queue => {
+                self.queue.push_back((msg.id, msg.addr));
+                println!("Salas cheias, {} na fila", self.queue.len());
+                self.notify_queue();
+            }
+        }
     }
 }
 
@@ -65,7 +166,25 @@ impl Handler<Disconnect> for Lobby {
     type Result = ();
 
     fn handle(&mut self, msg: Disconnect, _: &mut Context<Self>) {
-        self.sessions.remove(&msg.id);
+        // Saiu da fila antes de entrar
+        if let Some(position) = self.queue.iter().position(|(id, _)| *id == msg.id) {
+            self.queue.remove(position);
+            self.notify_queue();
+            return;
+        }
+
+        let Some(index) = self.members.remove(&msg.id) else {
+            return;
+        };
+        if let Some(room) = self.rooms.get_mut(&index) {
+            room.leave(msg.id);
+            // Salas extras que esvaziaram fecham; a room-1 fica sempre aberta
+            if room.is_empty() && index != 1 {
+                self.rooms.remove(&index);
+            }
+        }
+
+        self.drain_queue();
     }
 }
 
@@ -81,13 +200,106 @@ impl Handler<Broadcast> for Lobby {
     type Result = ();
 
     fn handle(&mut self, msg: Broadcast, _: &mut Context<Self>) {
-        // Manda para TODO MUNDO (inclusive quem enviou, para garantir sincronia total)
-        for (_, addr) in &self.sessions {
-            let _ = addr.do_send(WsMessage(msg.msg.clone()));
+        // Ignora mensagens atrasadas de quem já saiu (ou de quem ainda está na fila)
+        // Manda para TODO MUNDO da sala (inclusive quem enviou, para garantir sincronia total)
+        if let Some(room) = self.room_of(msg.id) {
+            room.send_all(&msg.msg);
+        }
+    }
+}
+
+// Mensagem de confirmação de snapshot (ack)
+#[derive(Message)]
+#[rtype(result = "()")]
+struct Ack {
+    id: Uuid,
+    tick: u64,
+}
+
+impl Handler<Ack> for Lobby {
+    type Result = ();
+
+    fn handle(&mut self, msg: Ack, _: &mut Context<Self>) {
+        if let Some(room) = self.room_of(msg.id) {
+            room.ack(msg.id, msg.tick);
         }
     }
 }
 
+// Mensagem de evento num canal
+#[derive(Message)]
+#[rtype(result = "()")]
+struct Publish {
+    id: Uuid,
+    channel: String,
+    data: serde_json::Value,
+}
+
+impl Handler<Publish> for Lobby {
+    type Result = ();
+
+    fn handle(&mut self, msg: Publish, _: &mut Context<Self>) {
+        if let Some(room) = self.room_of(msg.id) {
+            room.publish(msg.id, &msg.channel, msg.data);
+        }
+    }
+}
+
+// Mensagem para mexer na simulação (vinda de um clique)
+#[derive(Message)]
+#[rtype(result = "()")]
+struct Perturb {
+    id: Uuid,
+    pos: Position,
+}
+
+impl Handler<Perturb> for Lobby {
+    type Result = ();
+
+    fn handle(&mut self, msg: Perturb, _: &mut Context<Self>) {
+        if let Some(room) = self.room_of(msg.id) {
+            room.perturb(msg.pos.x, msg.pos.y);
+        }
+    }
+}
+
+// Pergunta ao Lobby como estão as salas (rota /rooms)
+#[derive(Serialize)]
+struct RoomStatus {
+    name: String,
+    participants: usize,
+}
+
+#[derive(Serialize)]
+struct LobbyStatus {
+    policy: RoomPolicy,
+    rooms: Vec<RoomStatus>,
+    waiting: usize,
+}
+
+#[derive(Message)]
+#[rtype(result = "LobbyStatus")]
+struct Status;
+
+impl Handler<Status> for Lobby {
+    type Result = MessageResult<Status>;
+
+    fn handle(&mut self, _: Status, _: &mut Context<Self>) -> Self::Result {
+        MessageResult(LobbyStatus {
+            policy: self.policy,
+            rooms: self
+                .rooms
+                .values()
+                .map(|room| RoomStatus {
+                    name: room.name.clone(),
+                    participants: room.len(),
+                })
+                .collect(),
+            waiting: self.queue.len(),
+        })
+    }
+}
+
 // --- A SESSÃO INDIVIDUAL (Cada Aba do Navegador) ---
 struct MyWs {
     id: Uuid,
@@ -118,6 +330,28 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for MyWs {
     fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
         match msg {
             Ok(ws::Message::Text(text)) => {
+                // Eventos da simulação e dos canais vão direto para o Lobby
+                match serde_json::from_str(&text) {
+                    Ok(ClientEvent::Perturb(pos)) => {
+                        self.lobby_addr.do_send(Perturb { id: self.id, pos });
+                        return;
+                    }
+                    Ok(ClientEvent::Ack { tick }) => {
+                        self.lobby_addr.do_send(Ack { id: self.id, tick });
+                        return;
+                    }
+                    Ok(ClientEvent::Event { channel, data }) => {
+                        self.lobby_addr.do_send(Publish { id: self.id, channel, data });
+                        return;
+                    }
+                    Err(_) => (),
+                }
+
+                // Snapshot, sala, fila e erro só saem do servidor
+                if rooms::is_server_type(&text) {
+                    return;
+                }
+
                 // MUDANÇA AQUI:
                 // Em vez de só repassar, a gente cria um JSON novo:
                 // { "id": "uuid-do-usuario", "data": {x: 10, y: 20} }
@@ -129,6 +363,11 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for MyWs {
                 });
             }
             Ok(ws::Message::Ping(msg)) => ctx.pong(&msg),
+            // Fecha na hora: a vaga na sala precisa liberar para quem está na fila
+            Ok(ws::Message::Close(reason)) => {
+                ctx.close(reason);
+                ctx.stop();
+            }
             _ => (),
         }
     }
@@ -143,7 +382,14 @@ impl Handler<WsMessage> for MyWs {
     }
 }
 
-// --- ROTA DE ENTRADA ---
+// --- ROTAS DE ENTRADA ---
+async fn rooms_status(lobby: web::Data<Addr<Lobby>>) -> HttpResponse {
+    match lobby.send(Status).await {
+        Ok(status) => HttpResponse::Ok().json(status),
+        Err(_) => HttpResponse::InternalServerError().finish(),
+    }
+}
+
 async fn ws_index(
     req: HttpRequest,
     stream: web::Payload,
@@ -158,19 +404,30 @@ async fn ws_index(
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    // Inicia o Lobby
-    let lobby = Lobby::new().start();
+    // Inicia o Lobby (capacidade das salas via ROOM_CAPACITY / ROOM_OVERFLOW)
+    let policy = RoomPolicy::from_env()?;
+    println!("Salas: até {} pessoas, excedente: {:?}", policy.capacity, policy.overflow);
+    let lobby = Lobby::new(policy).start();
     let lobby_data = web::Data::new(lobby);
 
-    println!("📡 Servidor Sync rodando em http://localhost:8080");
+    // TLS só se TLS_CERT e TLS_KEY estiverem definidas
+    let tls_config = tls_config::from_env()?;
+    let scheme = if tls_config.is_some() { "https" } else { "http" };
+
+    println!("📡 Servidor Sync rodando em {}://localhost:8080", scheme);
 
-    HttpServer::new(move || {
+    let server = HttpServer::new(move || {
         App::new()
             .app_data(lobby_data.clone())
             .route("/ws", web::get().to(ws_index)) // Rota do WebSocket
+            .route("/rooms", web::get().to(rooms_status)) // Ocupação das salas
             .service(actix_files::Files::new("/", "./static").index_file("index.html"))
-    })
-    .bind(("0.0.0.0", 8080))?
+    });
+
+    match tls_config {
+        Some(config) => server.bind_rustls_0_23(("0.0.0.0", 8080), config)?,
+        None => server.bind(("0.0.0.0", 8080))?,
+    }
     .run()
     .await
 }
\ No newline at end of file
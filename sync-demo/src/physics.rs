@@ -0,0 +1,131 @@
+// --- SIMULAÇÃO N-BODY DO SERVIDOR ---
+// Mesma lei de força do `cpu-nbody` (e do shader do `gpu-physics`), a do kernel nbody do
+// core-algo (kernels::nbody_force), só que com poucas partículas para caber num tick de rede.
+// Coordenadas normalizadas em [0, 1]: cada cliente escala para o tamanho da sua tela.
+
+use core_algo::kernels::nbody_force;
+use serde::Serialize;
+
+pub const NUM_PARTICLES: usize = 256;
+
+// Passo de tempo por tick. O `cpu-nbody` usa passo 1 (v += f; p += v), mas com
+// poucas partículas e coordenadas de tela isso explode; aqui a força é a mesma,
+// só integrada em passos menores.
+const DT: f32 = 0.05;
+
+// Amortecimento da velocidade quando a partícula bate na borda
+const WALL_BOUNCE: f32 = 0.8;
+
+// Até onde (em coordenadas normalizadas) um clique empurra as partículas
+const PERTURB_RADIUS: f32 = 0.15;
+const PERTURB_STRENGTH: f32 = 0.2;
+
+#[derive(Clone, Copy, Serialize, Debug)]
+pub struct Particle {
+    pub pos: [f32; 2],
+    pub vel: [f32; 2],
+}
+
+pub struct World {
+    pub particles: Vec<Particle>,
+    pub tick: u64,
+}
+
+impl World {
+    pub fn new(count: usize) -> Self {
+        // Diferente do `cpu-nbody` (todas em 0.5, 0.5), espalhamos num anel:
+        // com todas no mesmo ponto dx = dy = 0 e nada se mexe.
+        let particles = (0..count)
+            .map(|i| {
+                let angle = i as f32 / count as f32 * std::f32::consts::TAU;
+                let radius = 0.2 + 0.15 * ((i * 7919) % count) as f32 / count as f32;
+                Particle {
+                    pos: [0.5 + radius * angle.cos(), 0.5 + radius * angle.sin()],
+                    vel: [0.0, 0.0],
+                }
+            })
+            .collect();
+
+        World { particles, tick: 0 }
+    }
+
+    // Um passo da simulação (igual ao loop do `cpu-nbody`)
+    pub fn step(&mut self) {
+        // Cópia do estado anterior para leitura (Buffer A vs Buffer B na GPU)
+        let old_particles = self.particles.clone();
+
+        for p in self.particles.iter_mut() {
+            let [force_x, force_y] = nbody_force(p.pos, old_particles.iter().map(|o| o.pos));
+
+            p.vel[0] += force_x * DT;
+            p.vel[1] += force_y * DT;
+            p.pos[0] += p.vel[0] * DT;
+            p.pos[1] += p.vel[1] * DT;
+
+            // Mantém todo mundo dentro da "tela" (quica nas bordas)
+            for axis in 0..2 {
+                if p.pos[axis] < 0.0 || p.pos[axis] > 1.0 {
+                    p.pos[axis] = p.pos[axis].clamp(0.0, 1.0);
+                    p.vel[axis] = -p.vel[axis] * WALL_BOUNCE;
+                }
+            }
+        }
+
+        self.tick += 1;
+    }
+
+    // Clique de um cliente: empurra as partículas próximas para longe do ponto
+    pub fn perturb(&mut self, x: f32, y: f32) {
+        for p in self.particles.iter_mut() {
+            let dx = p.pos[0] - x;
+            let dy = p.pos[1] - y;
+            let dist = (dx * dx + dy * dy).sqrt();
+
+            if dist < PERTURB_RADIUS && dist > f32::EPSILON {
+                let falloff = 1.0 - dist / PERTURB_RADIUS;
+                p.vel[0] += dx / dist * PERTURB_STRENGTH * falloff;
+                p.vel[1] += dy / dist * PERTURB_STRENGTH * falloff;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world(particles: &[([f32; 2], [f32; 2])]) -> World {
+        World {
+            particles: particles.iter().map(|&(pos, vel)| Particle { pos, vel }).collect(),
+            tick: 0,
+        }
+    }
+
+    #[test]
+    fn symmetric_pair_keeps_center_of_mass() {
+        let mut world = world(&[([0.4, 0.5], [0.0, 0.0]), ([0.6, 0.5], [0.0, 0.0])]);
+        for _ in 0..10 {
+            world.step();
+        }
+        assert_eq!(world.tick, 10);
+
+        let [a, b] = [world.particles[0], world.particles[1]];
+        // Se atraem, cada uma o mesmo tanto para o meio
+        assert!(a.pos[0] > 0.4 && b.pos[0] < 0.6);
+        assert!(((a.pos[0] + b.pos[0]) / 2.0 - 0.5).abs() < 1e-6);
+        assert_eq!((a.pos[1], b.pos[1]), (0.5, 0.5));
+    }
+
+    #[test]
+    fn perturb_pushes_only_nearby_particles() {
+        let mut world = world(&[([0.5, 0.5], [0.0, 0.0]), ([0.55, 0.5], [0.0, 0.0]), ([0.9, 0.9], [0.0, 0.0])]);
+        world.perturb(0.5, 0.5);
+        let v: Vec<[f32; 2]> = world.particles.iter().map(|p| p.vel).collect();
+        // Em cima do clique (distância 0) e longe dele: nada muda
+        assert_eq!(v[0], [0.0, 0.0]);
+        assert_eq!(v[2], [0.0, 0.0]);
+        // A vizinha vai para longe do ponto (para +x)
+        assert!(v[1][0] > 0.0 && v[1][1] == 0.0);
+        assert_eq!(world.tick, 0);
+    }
+}
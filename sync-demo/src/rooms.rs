@@ -0,0 +1,286 @@
+// --- SALAS ---
+// Cada sala tem seus participantes, sua simulação e seus canais.
+// O Lobby distribui as conexões entre as salas seguindo uma política de capacidade:
+//   queue -> sala cheia, quem chega espera numa fila (e é avisado da posição)
+//   shard -> sala cheia, o Lobby abre room-2, room-3, ... automaticamente
+
+use crate::channels::Channels;
+use crate::delta::{QuantizedState, SnapshotHistory};
+use crate::physics::{self, World};
+use crate::WsMessage;
+use actix::Recipient;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io;
+use uuid::Uuid;
+
+pub const CAPACITY_ENV: &str = "ROOM_CAPACITY";
+pub const OVERFLOW_ENV: &str = "ROOM_OVERFLOW";
+
+const DEFAULT_CAPACITY: usize = 16;
+
+pub fn room_name(index: usize) -> String {
+    format!("room-{}", index)
+}
+
+// Mensagem do próprio servidor, no mesmo envelope { id, data } dos clientes
+pub fn server_event(data: Value) -> String {
+    json!({ "id": "server", "data": data }).to_string()
+}
+
+// Tipos que só o servidor manda: o repasse cru de um cliente não pode se passar por eles
+const SERVER_TYPES: [&str; 5] = ["world", "delta", "room", "queue", "error"];
+
+pub fn is_server_type(text: &str) -> bool {
+    serde_json::from_str::<Value>(text)
+        .ok()
+        .and_then(|v| v.get("type")?.as_str().map(|t| SERVER_TYPES.contains(&t)))
+        .unwrap_or(false)
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Overflow {
+    Queue,
+    Shard,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct RoomPolicy {
+    pub capacity: usize,
+    pub overflow: Overflow,
+}
+
+// Onde uma conexão nova deve ir
+#[derive(Debug, PartialEq)]
+pub enum Placement {
+    Join(usize),
+    Create(usize),
+    Queue,
+}
+
+impl Default for RoomPolicy {
+    fn default() -> Self {
+        RoomPolicy {
+            capacity: DEFAULT_CAPACITY,
+            overflow: Overflow::Shard,
+        }
+    }
+}
+
+impl RoomPolicy {
+    // ROOM_CAPACITY=N e ROOM_OVERFLOW=queue|shard (padrão: 16, shard)
+    pub fn from_env() -> io::Result<Self> {
+        let mut policy = RoomPolicy::default();
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidInput, msg);
+
+        if let Ok(capacity) = std::env::var(CAPACITY_ENV) {
+            policy.capacity = capacity
+                .parse()
+                .ok()
+                .filter(|c| *c > 0)
+                .ok_or_else(|| invalid(format!("{} inválido: {}", CAPACITY_ENV, capacity)))?;
+        }
+
+        if let Ok(overflow) = std::env::var(OVERFLOW_ENV) {
+            policy.overflow = match overflow.as_str() {
+                "queue" => Overflow::Queue,
+                "shard" => Overflow::Shard,
+                _ => return Err(invalid(format!("{} deve ser queue ou shard", OVERFLOW_ENV))),
+            };
+        }
+
+        Ok(policy)
+    }
+
+    // Decide a sala a partir da ocupação atual (índice da sala, participantes), em ordem
+    pub fn place(&self, rooms: impl IntoIterator<Item = (usize, usize)>) -> Placement {
+        let mut used = Vec::new();
+        for (index, len) in rooms {
+            if len < self.capacity {
+                return Placement::Join(index);
+            }
+            used.push(index);
+        }
+
+        // Nenhuma sala existe: abre a primeira, qualquer que seja a política
+        if used.is_empty() {
+            return Placement::Create(1);
+        }
+
+        match self.overflow {
+            Overflow::Queue => Placement::Queue,
+            // Menor número livre (room-2 pode ter fechado enquanto room-3 segue aberta)
+            Overflow::Shard => Placement::Create((1..).find(|i| !used.contains(i)).unwrap()),
+        }
+    }
+}
+
+pub struct Room {
+    pub name: String,
+    sessions: HashMap<Uuid, Recipient<WsMessage>>,
+    world: World,
+    // Últimos estados enviados + último tick confirmado por cada cliente
+    history: SnapshotHistory,
+    acks: HashMap<Uuid, u64>,
+    // Canais de eventos (chat com histórico, cursores, custom:*)
+    channels: Channels,
+}
+
+impl Room {
+    pub fn new(index: usize) -> Self {
+        Room {
+            name: room_name(index),
+            sessions: HashMap::new(),
+            world: World::new(physics::NUM_PARTICLES),
+            history: SnapshotHistory::new(),
+            acks: HashMap::new(),
+            channels: Channels::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    pub fn join(&mut self, id: Uuid, addr: Recipient<WsMessage>) {
+        // Quem chega recebe o histórico do chat e os últimos cursores
+        for event in self.channels.backlog() {
+            addr.do_send(WsMessage(event));
+        }
+        self.sessions.insert(id, addr);
+    }
+
+    pub fn leave(&mut self, id: Uuid) {
+        self.sessions.remove(&id);
+        self.acks.remove(&id);
+        self.channels.forget(id);
+    }
+
+    pub fn send_to(&self, id: Uuid, msg: String) {
+        if let Some(addr) = self.sessions.get(&id) {
+            addr.do_send(WsMessage(msg));
+        }
+    }
+
+    pub fn send_all(&self, msg: &str) {
+        for addr in self.sessions.values() {
+            addr.do_send(WsMessage(msg.to_owned()));
+        }
+    }
+
+    // Avança a simulação e manda o estado novo para a sala inteira
+    // (cada cliente recebe só a diferença desde o último tick que ele confirmou)
+    pub fn tick(&mut self) {
+        self.world.step();
+        self.history.push(QuantizedState::from_world(&self.world));
+
+        for (id, addr) in &self.sessions {
+            let Some(data) = self.history.encode_for(self.acks.get(id).copied()) else {
+                continue;
+            };
+            addr.do_send(WsMessage(format!(r#"{{"id": "server", "data": {} }}"#, data)));
+        }
+
+        // Canais "latest-only" (cursores) saem no ritmo do tick
+        for event in self.channels.flush() {
+            self.send_all(&event);
+        }
+    }
+
+    pub fn ack(&mut self, id: Uuid, tick: u64) {
+        // Só vale ack de um tick que ainda está no histórico: um tick inventado (ou do futuro)
+        // travaria o baseline e o cliente nunca mais receberia delta
+        if self.history.get(tick).is_none() {
+            return;
+        }
+        // Acks fora de ordem não podem voltar o baseline para trás
+        let acked = self.acks.entry(id).or_insert(tick);
+        if tick > *acked {
+            *acked = tick;
+        }
+    }
+
+    pub fn publish(&mut self, id: Uuid, channel: &str, data: Value) {
+        match self.channels.publish(id, channel, data) {
+            Ok(Some(event)) => self.send_all(&event),
+            // Vai no próximo tick
+            Ok(None) => (),
+            // Só quem mandou fica sabendo do erro
+            Err(e) => self.send_to(id, server_event(json!({ "type": "error", "message": e.to_string() }))),
+        }
+    }
+
+    pub fn perturb(&mut self, x: f32, y: f32) {
+        // Só aplica o empurrão; o próximo tick já espalha o resultado para todos
+        self.world.perturb(x, y);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::delta::Snapshot;
+
+    fn policy(capacity: usize, overflow: Overflow) -> RoomPolicy {
+        RoomPolicy { capacity, overflow }
+    }
+
+    #[test]
+    fn joins_first_room_with_space() {
+        let p = policy(2, Overflow::Shard);
+        assert_eq!(p.place([(1, 2), (2, 1)]), Placement::Join(2));
+        assert_eq!(p.place([(1, 0)]), Placement::Join(1));
+        assert_eq!(p.place([]), Placement::Create(1));
+    }
+
+    #[test]
+    fn shard_opens_lowest_free_room() {
+        let p = policy(2, Overflow::Shard);
+        assert_eq!(p.place([(1, 2)]), Placement::Create(2));
+        assert_eq!(p.place([(1, 2), (2, 2)]), Placement::Create(3));
+        assert_eq!(p.place([(1, 2), (3, 2)]), Placement::Create(2));
+    }
+
+    #[test]
+    fn queue_waits_when_every_room_is_full() {
+        let p = policy(1, Overflow::Queue);
+        assert_eq!(p.place([(1, 1)]), Placement::Queue);
+        assert_eq!(p.place([(1, 0)]), Placement::Join(1));
+    }
+
+    #[test]
+    fn clients_cannot_relay_server_types() {
+        assert!(is_server_type(r#"{"type":"world","tick":1,"particles":[]}"#));
+        assert!(is_server_type(r#"{"type":"error","message":"x"}"#));
+        assert!(!is_server_type(r#"{"type":"move","x":1,"y":2}"#));
+        assert!(!is_server_type("null"));
+    }
+
+    #[test]
+    fn ack_of_unknown_tick_is_ignored() {
+        let mut room = Room::new(1);
+        let id = Uuid::new_v4();
+        room.tick();
+        room.tick();
+
+        // Um tick que o servidor nunca mandou não vira baseline...
+        room.ack(id, u64::MAX);
+        assert_eq!(room.acks.get(&id), None);
+
+        // ...e o próximo ack de verdade continua valendo
+        let tick = room.history.latest().unwrap().tick;
+        room.ack(id, tick);
+        assert_eq!(room.acks.get(&id), Some(&tick));
+
+        room.tick();
+        let json = room.history.encode_for(room.acks.get(&id).copied()).unwrap();
+        let snapshot: Snapshot = serde_json::from_str(&json).unwrap();
+        assert!(matches!(snapshot, Snapshot::Delta { .. }));
+    }
+}